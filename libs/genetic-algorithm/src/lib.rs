@@ -1,11 +1,35 @@
+//! A genetic algorithm, along with a handful of selection, crossover and
+//! mutation methods to plug into it.
+//!
+//! # Features
+//!
+//! - `serde` - implements `Serialize` and `Deserialize` for [`Chromosome`],
+//!   which is (de)serialized as a flat list of genes.
+//! - `rayon` - adds [`GeneticAlgorithm::par_evolve_seeded`], which creates
+//!   children of the next generation in parallel.
+
+use lib_neural_network::NetworkTopology;
+use rand::distributions::WeightedError;
 use rand::seq::SliceRandom;
-use rand::{Rng, RngCore};
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::error::Error;
+use std::fmt;
 use std::ops::Index;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// See [`GeneticAlgorithm::with_repair()`]
+pub type RepairFn = dyn Fn(&mut Chromosome) + Send + Sync;
 
 pub struct GeneticAlgorithm<S> {
     selection_method: S,
     crossover_method: Box<dyn CrossoverMethod>,
     mutation_method: Box<dyn MutationMethod>,
+    elite_count: usize,
+    crossover_chance: f32,
+    gene_bounds: Option<(f32, f32)>,
+    repair: Option<Box<RepairFn>>,
+    gene_statistics: bool,
 }
 
 impl<S> GeneticAlgorithm<S>
@@ -21,313 +45,3245 @@ where
             selection_method,
             crossover_method: Box::new(crossover_method),
             mutation_method: Box::new(mutation_method),
+            elite_count: 0,
+            crossover_chance: 1.0,
+            gene_bounds: None,
+            repair: None,
+            gene_statistics: false,
         }
     }
 
-    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
-    where
-        I: Individual,
-    {
-        assert!(!population.is_empty());
+    /// Copies the `elite_count` fittest individuals into each new generation
+    /// verbatim, so that the best chromosomes can't get lost
+    pub fn with_elite_count(mut self, elite_count: usize) -> Self {
+        self.elite_count = elite_count;
+        self
+    }
 
-        let new_population = (0..population.len())
-            .map(|_| {
-                let parent_a = self.selection_method.select(rng, population).chromosome();
-                let parent_b = self.selection_method.select(rng, population).chromosome();
+    /// Performs crossover only with given probability - otherwise the child
+    /// starts as a copy of its first parent (before being mutated)
+    pub fn with_crossover_chance(mut self, crossover_chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&crossover_chance));
 
-                let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
+        self.crossover_chance = crossover_chance;
+        self
+    }
 
-                self.mutation_method.mutate(rng, &mut child);
+    /// Clamps each gene of every child into `[min, max]` after mutation,
+    /// whichever crossover and mutation methods are used
+    pub fn with_gene_bounds(mut self, min: f32, max: f32) -> Self {
+        assert!(min <= max);
 
-                I::create(child)
-            })
-            .collect();
-        let stats = Statistics::new(population);
+        self.gene_bounds = Some((min, max));
+        self
+    }
 
-        (new_population, stats)
+    /// Calls given function on every child, after mutation and gene bounds,
+    /// e.g. to repair chromosomes violating some constraints of the encoding
+    pub fn with_repair(mut self, repair: impl Fn(&mut Chromosome) + Send + Sync + 'static) -> Self {
+        self.repair = Some(Box::new(repair));
+        self
     }
-}
 
-pub trait Individual {
-    fn fitness(&self) -> f32;
-    fn chromosome(&self) -> &Chromosome;
-    fn create(chromosome: Chromosome) -> Self;
-}
+    /// Makes [`Statistics`] include mean & variance of each gene across the
+    /// population, see [`Statistics::gene_means()`]; that's another pass
+    /// over all the genes, so it's off by default
+    pub fn with_gene_statistics(mut self, gene_statistics: bool) -> Self {
+        self.gene_statistics = gene_statistics;
+        self
+    }
 
-pub trait SelectionMethod {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
-    where
-        I: Individual;
-}
+    /// Tells the mutation method which generation is about to be evolved,
+    /// e.g. so that it can follow a schedule; see [`DecayingGaussianMutation`]
+    pub fn set_generation(&mut self, generation: usize) {
+        self.mutation_method.set_generation(generation);
+    }
 
-pub struct RouletteWheelSelection;
-impl SelectionMethod for RouletteWheelSelection {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    /// Same as `try_evolve`, but panics if the population is empty
+    pub fn evolve<I>(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
     where
         I: Individual,
     {
-        population
-            .choose_weighted(rng, |individual| individual.fitness())
-            .expect("got an empty population")
+        self.try_evolve(rng, population)
+            .unwrap_or_else(|err| panic!("{}", err))
     }
-}
 
-#[derive(Clone, Debug)]
-pub struct Chromosome {
-    genes: Vec<f32>,
-}
+    pub fn try_evolve<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Statistics), EvolveError>
+    where
+        I: Individual,
+    {
+        if population.is_empty() {
+            return Err(EvolveError::EmptyPopulation);
+        }
 
-impl Chromosome {
-    pub fn len(&self) -> usize {
-        self.genes.len()
+        let evolution = self.evolve_detailed(rng, population);
+
+        Ok((evolution.population, evolution.stats))
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &f32> {
-        self.genes.iter()
+    pub fn evolve_detailed<I>(&mut self, rng: &mut dyn RngCore, population: &[I]) -> Evolution<I>
+    where
+        I: Individual,
+    {
+        assert!(!population.is_empty());
+
+        self.on_generation(population);
+
+        let elites = self.elites(population);
+        let elite_count = elites.len();
+
+        let elites = elites.into_iter().map(|individual| {
+            let fitness = individual.fitness();
+
+            (
+                I::create(individual.chromosome().clone()),
+                (fitness, fitness),
+            )
+        });
+
+        let parents = self.selection_method.select_many(
+            rng,
+            population,
+            2 * (population.len() - elite_count),
+        );
+
+        let offspring = parents.chunks_exact(2).map(|parents| {
+            let (parent_a, parent_b) = (parents[0], parents[1]);
+            let child = self.breed(rng, parent_a.chromosome(), parent_b.chromosome());
+
+            (I::create(child), (parent_a.fitness(), parent_b.fitness()))
+        });
+
+        let (new_population, parent_fitnesses) = elites.chain(offspring).unzip();
+        let stats = self.statistics(population);
+
+        Evolution {
+            population: new_population,
+            parent_fitnesses,
+            stats,
+        }
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
-        self.genes.iter_mut()
+    /// Like `evolve`, but instead of sharing one RNG, each child gets its own
+    /// one, seeded with `seed + child index` - so that the outcome doesn't
+    /// depend on the order in which children get created.
+    ///
+    /// Parents are still drawn all at once (see
+    /// [`SelectionMethod::select_many()`]), before any child gets created,
+    /// through a separate stream of the RNG seeded with `seed`.
+    ///
+    /// See `par_evolve_seeded` for the parallel counterpart.
+    pub fn evolve_seeded<I>(&mut self, seed: u64, population: &[I]) -> (Vec<I>, Statistics)
+    where
+        I: Individual,
+    {
+        assert!(!population.is_empty());
+
+        self.on_generation(population);
+
+        let elites = self.elites(population);
+        let parents = self.select_seeded(seed, population, elites.len());
+
+        let offspring: Vec<_> = parents
+            .chunks_exact(2)
+            .enumerate()
+            .map(|(n, parents)| self.breed_seeded(seed, elites.len() + n, parents))
+            .collect();
+
+        self.finish_seeded(elites, offspring, population)
     }
-}
 
-impl Index<usize> for Chromosome {
-    type Output = f32;
+    #[cfg(feature = "rayon")]
+    pub fn par_evolve_seeded<I>(&mut self, seed: u64, population: &[I]) -> (Vec<I>, Statistics)
+    where
+        S: Sync,
+        I: Individual + Send + Sync,
+    {
+        use rayon::prelude::*;
 
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.genes[index]
+        assert!(!population.is_empty());
+
+        self.on_generation(population);
+
+        let elites = self.elites(population);
+        let parents = self.select_seeded(seed, population, elites.len());
+
+        let offspring: Vec<_> = parents
+            .par_chunks_exact(2)
+            .enumerate()
+            .map(|(n, parents)| self.breed_seeded(seed, elites.len() + n, parents))
+            .collect();
+
+        self.finish_seeded(elites, offspring, population)
     }
-}
 
-impl FromIterator<f32> for Chromosome {
-    fn from_iter<T: IntoIterator<Item = f32>>(iter: T) -> Self {
-        Self {
-            genes: iter.into_iter().collect(),
+    fn on_generation<I>(&mut self, population: &[I])
+    where
+        I: Individual,
+    {
+        // Diversity takes a pass over all the genes, so let's not compute it
+        // for mutation methods that would ignore it anyway
+        if self.mutation_method.wants_diversity() {
+            self.mutation_method.on_generation(diversity(population));
         }
     }
-}
 
-impl IntoIterator for Chromosome {
-    type Item = f32;
-    type IntoIter = std::vec::IntoIter<f32>;
+    fn elites<'a, I>(&self, population: &'a [I]) -> Vec<&'a I>
+    where
+        I: Individual,
+    {
+        if self.elite_count == 0 {
+            return Vec::new();
+        }
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.genes.into_iter()
+        let mut ranked: Vec<_> = population.iter().collect();
+        ranked.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+        ranked.truncate(self.elite_count);
+        ranked
     }
-}
 
-pub trait CrossoverMethod {
-    fn crossover(
+    fn breed(
         &self,
         rng: &mut dyn RngCore,
         parent_a: &Chromosome,
         parent_b: &Chromosome,
-    ) -> Chromosome;
-}
+    ) -> Chromosome {
+        let mut child = if rng.gen_bool(self.crossover_chance as f64) {
+            self.crossover_method.crossover(rng, parent_a, parent_b)
+        } else {
+            parent_a.clone()
+        };
 
-pub struct UniformCrossover;
+        self.mutation_method.mutate(rng, &mut child);
 
-impl CrossoverMethod for UniformCrossover {
-    fn crossover(
-        &self,
-        rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-    ) -> Chromosome {
-        assert_eq!(parent_a.len(), parent_b.len());
+        if let Some((min, max)) = self.gene_bounds {
+            for gene in child.iter_mut() {
+                *gene = gene.clamp(min, max);
+            }
+        }
 
-        parent_a
-            .iter()
-            .zip(parent_b.iter())
-            .map(|(&a, &b)| if rng.gen_bool(0.5) { a } else { b })
-            .collect()
+        if let Some(repair) = &self.repair {
+            repair(&mut child);
+        }
+
+        child
     }
-}
 
-pub trait MutationMethod {
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
-}
+    /// Draws parents of all the non-elite children (two per child)
+    fn select_seeded<'a, I>(&self, seed: u64, population: &'a [I], elite_count: usize) -> Vec<&'a I>
+    where
+        I: Individual,
+    {
+        // Children use the default stream, so this one can't collide with
+        // any of them
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        rng.set_stream(1);
+
+        self.selection_method.select_many(
+            &mut rng,
+            population,
+            2 * (population.len() - elite_count),
+        )
+    }
 
-pub struct GaussianMutation {
-    chance: f32,
-    coeff: f32,
-}
+    fn breed_seeded<I>(&self, seed: u64, idx: usize, parents: &[&I]) -> I
+    where
+        I: Individual,
+    {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed.wrapping_add(idx as u64));
 
-impl GaussianMutation {
-    pub fn new(chance: f32, coeff: f32) -> Self {
-        assert!(chance >= 0.0 && chance <= 1.0);
+        I::create(self.breed(&mut rng, parents[0].chromosome(), parents[1].chromosome()))
+    }
+
+    fn finish_seeded<I>(
+        &self,
+        elites: Vec<&I>,
+        offspring: Vec<I>,
+        population: &[I],
+    ) -> (Vec<I>, Statistics)
+    where
+        I: Individual,
+    {
+        let new_population = elites
+            .into_iter()
+            .map(|individual| I::create(individual.chromosome().clone()))
+            .chain(offspring)
+            .collect();
+
+        (new_population, self.statistics(population))
+    }
+
+    fn statistics<I>(&self, population: &[I]) -> Statistics
+    where
+        I: Individual,
+    {
+        let stats = Statistics::new(population);
 
-        Self { chance, coeff }
+        if self.gene_statistics {
+            stats.with_genes(population)
+        } else {
+            stats
+        }
     }
 }
 
-impl MutationMethod for GaussianMutation {
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
-        for gene in child.iter_mut() {
-            let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
+/// Builds a [`GeneticAlgorithm`], starting from roulette-wheel selection,
+/// uniform crossover, a light gaussian mutation, no elitism and a crossover
+/// chance of 100%.
+///
+/// ```
+/// use lib_genetic_algorithm::*;
+///
+/// let ga = GeneticAlgorithmBuilder::new()
+///     .selection(TournamentSelection::new(3))
+///     .crossover(SinglePointCrossover)
+///     .mutation(GaussianMutation::new(0.01, 0.3))
+///     .elite_count(2)
+///     .crossover_chance(0.9)
+///     .build();
+/// ```
+pub struct GeneticAlgorithmBuilder<S> {
+    selection_method: S,
+    crossover_method: Box<dyn CrossoverMethod>,
+    mutation_method: Box<dyn MutationMethod>,
+    elite_count: usize,
+    crossover_chance: f32,
+    gene_bounds: Option<(f32, f32)>,
+    repair: Option<Box<RepairFn>>,
+    gene_statistics: bool,
+}
 
-            if rng.gen_bool(self.chance as f64) {
-                *gene += sign * self.coeff * rng.gen::<f32>()
-            }
+impl GeneticAlgorithmBuilder<RouletteWheelSelection> {
+    pub fn new() -> Self {
+        Self {
+            selection_method: RouletteWheelSelection::new(),
+            crossover_method: Box::new(UniformCrossover),
+            mutation_method: Box::new(GaussianMutation::new(0.01, 0.3)),
+            elite_count: 0,
+            crossover_chance: 1.0,
+            gene_bounds: None,
+            repair: None,
+            gene_statistics: false,
         }
     }
 }
 
-pub struct Statistics {
-    pub min_fitness: f32,
-    pub max_fitness: f32,
-    pub avg_fitness: f32,
+impl Default for GeneticAlgorithmBuilder<RouletteWheelSelection> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl Statistics {
-    fn new<I>(population: &[I]) -> Self
+impl<S> GeneticAlgorithmBuilder<S>
+where
+    S: SelectionMethod,
+{
+    pub fn selection<S2>(self, selection_method: S2) -> GeneticAlgorithmBuilder<S2>
     where
-        I: Individual,
+        S2: SelectionMethod,
     {
-        assert!(!population.is_empty());
+        GeneticAlgorithmBuilder {
+            selection_method,
+            crossover_method: self.crossover_method,
+            mutation_method: self.mutation_method,
+            elite_count: self.elite_count,
+            crossover_chance: self.crossover_chance,
+            gene_bounds: self.gene_bounds,
+            repair: self.repair,
+            gene_statistics: self.gene_statistics,
+        }
+    }
 
-        let mut min_fitness = population[0].fitness();
-        let mut max_fitness = min_fitness;
-        let mut sum_fitness = 0.0;
+    pub fn crossover(mut self, crossover_method: impl CrossoverMethod + 'static) -> Self {
+        self.crossover_method = Box::new(crossover_method);
+        self
+    }
 
-        for individual in population {
-            let fitness = individual.fitness();
+    pub fn mutation(mut self, mutation_method: impl MutationMethod + 'static) -> Self {
+        self.mutation_method = Box::new(mutation_method);
+        self
+    }
+
+    pub fn elite_count(mut self, elite_count: usize) -> Self {
+        self.elite_count = elite_count;
+        self
+    }
+
+    pub fn crossover_chance(mut self, crossover_chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&crossover_chance));
+
+        self.crossover_chance = crossover_chance;
+        self
+    }
+
+    pub fn gene_bounds(mut self, min: f32, max: f32) -> Self {
+        assert!(min <= max);
+
+        self.gene_bounds = Some((min, max));
+        self
+    }
+
+    pub fn repair(mut self, repair: impl Fn(&mut Chromosome) + Send + Sync + 'static) -> Self {
+        self.repair = Some(Box::new(repair));
+        self
+    }
+
+    pub fn gene_statistics(mut self, gene_statistics: bool) -> Self {
+        self.gene_statistics = gene_statistics;
+        self
+    }
 
-            min_fitness = min_fitness.min(fitness);
-            max_fitness = max_fitness.max(fitness);
-            sum_fitness += fitness;
+    pub fn build(self) -> GeneticAlgorithm<S> {
+        GeneticAlgorithm {
+            selection_method: self.selection_method,
+            crossover_method: self.crossover_method,
+            mutation_method: self.mutation_method,
+            elite_count: self.elite_count,
+            crossover_chance: self.crossover_chance,
+            gene_bounds: self.gene_bounds,
+            repair: self.repair,
+            gene_statistics: self.gene_statistics,
         }
+    }
+}
+
+pub struct Evolution<I> {
+    pub population: Vec<I>,
+
+    /// Fitnesses of both parents of each child, in the same order as
+    /// `population`; elites are reported as their own parents
+    pub parent_fitnesses: Vec<(f32, f32)>,
+
+    pub stats: Statistics,
+}
+
+/// Evolves several subpopulations ("islands") independently, every now and
+/// then sending the fittest individuals of each island to its neighbor (with
+/// islands forming a ring), which helps to keep the populations diverse
+pub struct IslandModel<S, I> {
+    ga: GeneticAlgorithm<S>,
+    islands: Vec<Vec<I>>,
+    migration_interval: usize,
+    migrant_count: usize,
+    generation: usize,
+}
+
+impl<S, I> IslandModel<S, I>
+where
+    S: SelectionMethod,
+    I: Individual + Clone,
+{
+    /// Every `migration_interval`-th call to [`Self::evolve()`] first copies
+    /// the `migrant_count` fittest individuals of each island over the least
+    /// fit ones of the next island
+    pub fn new(
+        ga: GeneticAlgorithm<S>,
+        islands: Vec<Vec<I>>,
+        migration_interval: usize,
+        migrant_count: usize,
+    ) -> Self {
+        assert!(!islands.is_empty());
+        assert!(migration_interval > 0);
 
         Self {
-            min_fitness,
-            max_fitness,
-            avg_fitness: sum_fitness / (population.len() as f32),
+            ga,
+            islands,
+            migration_interval,
+            migrant_count,
+            generation: 0,
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use rand::SeedableRng;
-    use rand_chacha::ChaCha8Rng;
-    use std::collections::BTreeMap;
-    use std::iter::FromIterator;
+    pub fn islands(&self) -> &[Vec<I>] {
+        &self.islands
+    }
 
-    #[derive(Debug, Clone, PartialEq)]
-    enum TestIndividual {
-        WithChromosome { chromosome: Chromosome },
-        WithFitness { fitness: f32 },
+    /// Gives access to the islands, e.g. for evaluating their individuals
+    /// before the next [`Self::evolve()`]
+    pub fn islands_mut(&mut self) -> &mut [Vec<I>] {
+        &mut self.islands
     }
 
-    impl TestIndividual {
-        fn new(fitness: f32) -> Self {
-            Self::WithFitness { fitness }
-        }
+    pub fn generation(&self) -> usize {
+        self.generation
     }
 
-    impl Individual for TestIndividual {
-        fn create(chromosome: Chromosome) -> Self {
-            TestIndividual::WithChromosome { chromosome }
+    /// Evolves each of the islands, returning their statistics (in the same
+    /// order as the islands)
+    pub fn evolve(&mut self, rng: &mut dyn RngCore) -> Vec<Statistics> {
+        self.generation += 1;
+
+        if self.generation.is_multiple_of(self.migration_interval) {
+            self.migrate();
         }
 
-        fn chromosome(&self) -> &Chromosome {
-            match self {
-                Self::WithChromosome { chromosome } => chromosome,
-                Self::WithFitness { .. } => {
-                    panic!("not supported for TestIndividual::WithFitness")
-                }
-            }
+        let mut stats = Vec::with_capacity(self.islands.len());
+
+        for island in &mut self.islands {
+            let (population, island_stats) = self.ga.evolve(rng, island);
+
+            *island = population;
+            stats.push(island_stats);
         }
 
-        fn fitness(&self) -> f32 {
-            match self {
-                Self::WithChromosome { chromosome } => chromosome.iter().sum(),
-                Self::WithFitness { fitness } => *fitness,
+        stats
+    }
+
+    fn migrate(&mut self) {
+        let migrants: Vec<Vec<I>> = self
+            .islands
+            .iter()
+            .map(|island| {
+                let mut ranked: Vec<_> = island.iter().collect();
+                ranked.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+                ranked.truncate(self.migrant_count);
+                ranked.into_iter().cloned().collect()
+            })
+            .collect();
+
+        let n = self.islands.len();
+
+        for (idx, migrants) in migrants.into_iter().enumerate() {
+            let island = &mut self.islands[(idx + 1) % n];
+
+            island.sort_by(|a, b| a.fitness().total_cmp(&b.fitness()));
+
+            for (individual, migrant) in island.iter_mut().zip(migrants) {
+                *individual = migrant;
             }
         }
     }
+}
 
-    impl PartialEq for Chromosome {
-        fn eq(&self, other: &Self) -> bool {
-            approx::relative_eq!(self.genes.as_slice(), other.genes.as_slice())
+/// Differential evolution (DE/rand/1/bin) - an alternative to the genetic
+/// algorithm, which creates each child by adding the scaled difference of two
+/// random chromosomes to a third one and crossing the result over with the
+/// child's "target" chromosome.
+///
+/// Since children's fitness isn't known until they get evaluated, the usual
+/// greedy selection (where a child replaces its target only if it's at least
+/// as fit) is deferred: each call to [`Self::evolve()`] first compares the
+/// given (evaluated) children with their targets remembered from the previous
+/// call, and only then creates new children out of the survivors.
+pub struct DifferentialEvolution {
+    f: f32,
+    cr: f32,
+    targets: Vec<(Chromosome, f32)>,
+}
+
+impl DifferentialEvolution {
+    /// - `f` - scale of the difference vector, usually from `[0.4, 1.0]`,
+    /// - `cr` - probability of taking a gene from the mutant instead of the
+    ///   target.
+    pub fn new(f: f32, cr: f32) -> Self {
+        assert!(f > 0.0 && f <= 2.0);
+        assert!((0.0..=1.0).contains(&cr));
+
+        Self {
+            f,
+            cr,
+            targets: Vec::new(),
+        }
+    }
+
+    pub fn evolve<I>(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
+    where
+        I: Individual,
+    {
+        assert!(population.len() >= 4, "got less than 4 individuals");
+
+        let evaluated = population
+            .iter()
+            .map(|individual| (individual.chromosome().clone(), individual.fitness()));
+
+        // A population of a different size can't have come from our previous
+        // call, so there's nothing to compare it with
+        let survivors: Vec<_> = if self.targets.len() == population.len() {
+            self.targets
+                .drain(..)
+                .zip(evaluated)
+                .map(|(target, child)| if child.1 >= target.1 { child } else { target })
+                .collect()
+        } else {
+            evaluated.collect()
+        };
+
+        let children = (0..survivors.len())
+            .map(|idx| I::create(self.child(rng, &survivors, idx)))
+            .collect();
+
+        self.targets = survivors;
+
+        (children, Statistics::new(population))
+    }
+
+    fn child(
+        &self,
+        rng: &mut dyn RngCore,
+        survivors: &[(Chromosome, f32)],
+        target_idx: usize,
+    ) -> Chromosome {
+        // Three distinct individuals, none of them being the target
+        let others: Vec<_> = rand::seq::index::sample(rng, survivors.len() - 1, 3)
+            .into_iter()
+            .map(|idx| if idx >= target_idx { idx + 1 } else { idx })
+            .map(|idx| &survivors[idx].0)
+            .collect();
+
+        let (a, b, c) = (others[0], others[1], others[2]);
+        let target = &survivors[target_idx].0;
+
+        // At least one gene always comes from the mutant, so that the child
+        // differs from its target
+        let forced = rng.gen_range(0..target.len().max(1));
+
+        (0..target.len())
+            .map(|idx| {
+                if idx == forced || rng.gen_bool(self.cr as f64) {
+                    a[idx] + self.f * (b[idx] - c[idx])
+                } else {
+                    target[idx]
+                }
+            })
+            .collect()
+    }
+}
+
+pub trait Individual {
+    fn fitness(&self) -> f32;
+    fn chromosome(&self) -> &Chromosome;
+    fn create(chromosome: Chromosome) -> Self;
+}
+
+pub trait SelectionMethod {
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual;
+
+    fn select_many<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I], n: usize) -> Vec<&'a I>
+    where
+        I: Individual,
+    {
+        (0..n).map(|_| self.select(rng, population)).collect()
+    }
+}
+
+#[derive(Default)]
+pub struct RouletteWheelSelection {
+    scaling: Option<Box<dyn FitnessScaling>>,
+    buckets: Option<u32>,
+}
+
+impl RouletteWheelSelection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Transforms each individual's fitness with given scaling before
+    /// spinning the wheel
+    pub fn with_scaling(scaling: impl FitnessScaling + 'static) -> Self {
+        Self {
+            scaling: Some(Box::new(scaling)),
+            buckets: None,
+        }
+    }
+
+    /// Spins the wheel over integer weights instead: each fitness gets
+    /// scaled into `0..=buckets`, relative to the best one, which makes the
+    /// draw independent of how floats get summed on given platform
+    pub fn quantized(buckets: u32) -> Self {
+        assert!(buckets > 0);
+
+        Self {
+            scaling: None,
+            buckets: Some(buckets),
+        }
+    }
+}
+
+impl SelectionMethod for RouletteWheelSelection {
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual,
+    {
+        assert!(!population.is_empty(), "got an empty population");
+
+        let weight = |individual: &I| {
+            let fitness = match &self.scaling {
+                Some(scaling) => scaling.scale(individual.fitness()),
+                None => individual.fitness(),
+            };
+
+            fitness.max(0.0)
+        };
+
+        let chosen = match self.buckets {
+            Some(buckets) => {
+                let max = population.iter().map(weight).fold(0.0, f32::max);
+
+                population.choose_weighted(rng, |individual| {
+                    if max > 0.0 {
+                        (weight(individual) / max * buckets as f32).round() as u64
+                    } else {
+                        0
+                    }
+                })
+            }
+
+            None => population.choose_weighted(rng, weight),
+        };
+
+        match chosen {
+            Ok(individual) => individual,
+
+            // Happens e.g. early in the simulation, when no bird has eaten
+            // anything yet - there's no one to favor, so pick anyone
+            Err(WeightedError::AllWeightsZero) => {
+                population.choose(rng).expect("got an empty population")
+            }
+
+            Err(err) => panic!("couldn't select an individual: {}", err),
+        }
+    }
+}
+
+pub trait FitnessScaling: Send + Sync {
+    fn scale(&self, raw: f32) -> f32;
+}
+
+/// `a * raw + b`
+pub struct LinearScaling {
+    a: f32,
+    b: f32,
+}
+
+impl LinearScaling {
+    pub fn new(a: f32, b: f32) -> Self {
+        Self { a, b }
+    }
+}
+
+impl FitnessScaling for LinearScaling {
+    fn scale(&self, raw: f32) -> f32 {
+        self.a * raw + self.b
+    }
+}
+
+/// `raw ^ exponent`
+pub struct PowerScaling {
+    exponent: f32,
+}
+
+impl PowerScaling {
+    pub fn new(exponent: f32) -> Self {
+        Self { exponent }
+    }
+}
+
+impl FitnessScaling for PowerScaling {
+    fn scale(&self, raw: f32) -> f32 {
+        raw.powf(self.exponent)
+    }
+}
+
+/// `1 / (1 + e^(-steepness * (raw - midpoint)))`
+pub struct SigmoidScaling {
+    steepness: f32,
+    midpoint: f32,
+}
+
+impl SigmoidScaling {
+    pub fn new(steepness: f32, midpoint: f32) -> Self {
+        Self {
+            steepness,
+            midpoint,
+        }
+    }
+}
+
+impl FitnessScaling for SigmoidScaling {
+    fn scale(&self, raw: f32) -> f32 {
+        1.0 / (1.0 + (-self.steepness * (raw - self.midpoint)).exp())
+    }
+}
+
+/// Like roulette-wheel selection, but instead of spinning the wheel `n`
+/// times, spins it once with `n` evenly spaced pointers - so that the
+/// selected individuals match their fitness proportions much more tightly
+pub struct StochasticUniversalSampling;
+
+impl SelectionMethod for StochasticUniversalSampling {
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual,
+    {
+        self.select_many(rng, population, 1)[0]
+    }
+
+    fn select_many<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I], n: usize) -> Vec<&'a I>
+    where
+        I: Individual,
+    {
+        assert!(!population.is_empty(), "got an empty population");
+
+        let weights: Vec<_> = population
+            .iter()
+            .map(|individual| individual.fitness().max(0.0))
+            .collect();
+
+        let total: f32 = weights.iter().sum();
+
+        if n == 0 {
+            return Vec::new();
+        }
+
+        if total <= 0.0 {
+            return (0..n)
+                .map(|_| population.choose(rng).expect("got an empty population"))
+                .collect();
+        }
+
+        let step = total / (n as f32);
+        let start = rng.gen_range(0.0..step);
+
+        let mut selected = Vec::with_capacity(n);
+        let mut idx = 0;
+        let mut cumulative = weights[0];
+
+        for i in 0..n {
+            let pointer = start + (i as f32) * step;
+
+            while cumulative < pointer && idx < population.len() - 1 {
+                idx += 1;
+                cumulative += weights[idx];
+            }
+
+            selected.push(&population[idx]);
+        }
+
+        // Pointers walk the wheel in order, so without shuffling, parent
+        // pairs would almost always consist of the same individual
+        selected.shuffle(rng);
+        selected
+    }
+}
+
+pub struct TruncationSelection {
+    fraction: f32,
+}
+
+impl TruncationSelection {
+    pub fn new(fraction: f32) -> Self {
+        assert!(fraction > 0.0 && fraction <= 1.0);
+
+        Self { fraction }
+    }
+}
+
+impl SelectionMethod for TruncationSelection {
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual,
+    {
+        let mut ranked: Vec<_> = population.iter().collect();
+        ranked.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+
+        let len = ((population.len() as f32) * self.fraction).ceil() as usize;
+        ranked.truncate(len.max(1));
+
+        ranked.choose(rng).expect("got an empty population")
+    }
+}
+
+pub struct TournamentSelection {
+    size: usize,
+}
+
+impl TournamentSelection {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0);
+
+        Self { size }
+    }
+}
+
+impl SelectionMethod for TournamentSelection {
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual,
+    {
+        // If the tournament is bigger than the population, everyone takes part
+        let size = self.size.min(population.len());
+
+        population
+            .choose_multiple(rng, size)
+            .max_by(|a, b| a.fitness().total_cmp(&b.fitness()))
+            .expect("got an empty population")
+    }
+}
+
+pub struct RankSelection {
+    selective_pressure: f32,
+}
+
+impl RankSelection {
+    pub fn new(selective_pressure: f32) -> Self {
+        assert!((1.0..=2.0).contains(&selective_pressure));
+
+        Self { selective_pressure }
+    }
+}
+
+impl SelectionMethod for RankSelection {
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual,
+    {
+        if let [individual] = population {
+            return individual;
+        }
+
+        let mut ranked: Vec<_> = population.iter().collect();
+        ranked.sort_by(|a, b| a.fitness().total_cmp(&b.fitness()));
+
+        let max_rank = ranked.len().saturating_sub(1) as f32;
+        let sp = self.selective_pressure;
+
+        // Linear ranking: the weakest individual gets `2 - sp`, the fittest
+        // one gets `sp` and everyone else lies evenly in between
+        let ranks: Vec<_> = (0..ranked.len()).collect();
+
+        let rank = ranks
+            .choose_weighted(rng, |&rank| {
+                (2.0 - sp) + 2.0 * (sp - 1.0) * (rank as f32) / max_rank
+            })
+            .expect("got an empty population");
+
+        ranked[*rank]
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Chromosome {
+    genes: Vec<f32>,
+}
+
+impl Chromosome {
+    pub fn new(genes: Vec<f32>) -> Self {
+        Self { genes }
+    }
+
+    pub fn try_new(genes: Vec<f32>) -> Result<Self, ChromosomeError> {
+        if let Some(index) = genes.iter().position(|gene| gene.is_nan()) {
+            return Err(ChromosomeError::NanGene { index });
+        }
+
+        Ok(Self::new(genes))
+    }
+
+    pub fn len(&self) -> usize {
+        self.genes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.genes.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        &self.genes
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &f32> {
+        self.genes.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+        self.genes.iter_mut()
+    }
+
+    /// Returns indices at which chromosome of a neural network with given
+    /// topology can be cut without splitting any of its layers, i.e. where
+    /// genes of one layer end and genes of the next one begin; see
+    /// [`LayerAlignedCrossover`]
+    pub fn crossover_points(topology: &NetworkTopology) -> Vec<usize> {
+        let mut points: Vec<_> = topology
+            .layer_ranges()
+            .into_iter()
+            .map(|range| range.end)
+            .collect();
+
+        // The last layer ends where the chromosome does, which is no cut
+        points.pop();
+        points
+    }
+
+    pub fn euclidean_distance(&self, other: &Chromosome) -> f32 {
+        assert_eq!(self.len(), other.len());
+
+        self.iter()
+            .zip(other.iter())
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f32>()
+            .sqrt()
+    }
+
+    /// Returns the centroid of given chromosomes, i.e. a chromosome whose each
+    /// gene is the mean of the corresponding genes
+    pub fn mean(chromosomes: &[Chromosome]) -> Chromosome {
+        assert!(!chromosomes.is_empty());
+
+        let len = chromosomes[0].len();
+        let mut genes = vec![0.0; len];
+
+        for chromosome in chromosomes {
+            assert_eq!(chromosome.len(), len);
+
+            for (sum, gene) in genes.iter_mut().zip(chromosome.iter()) {
+                *sum += gene;
+            }
+        }
+
+        for gene in &mut genes {
+            *gene /= chromosomes.len() as f32;
+        }
+
+        Self::new(genes)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChromosomeError {
+    NanGene { index: usize },
+}
+
+impl fmt::Display for ChromosomeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NanGene { index } => write!(f, "gene #{} is NaN", index),
+        }
+    }
+}
+
+impl Error for ChromosomeError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EvolveError {
+    EmptyPopulation,
+}
+
+impl fmt::Display for EvolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyPopulation => write!(f, "got an empty population"),
+        }
+    }
+}
+
+impl Error for EvolveError {}
+
+impl Index<usize> for Chromosome {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.genes[index]
+    }
+}
+
+impl FromIterator<f32> for Chromosome {
+    fn from_iter<T: IntoIterator<Item = f32>>(iter: T) -> Self {
+        Self {
+            genes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for Chromosome {
+    type Item = f32;
+    type IntoIter = std::vec::IntoIter<f32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.genes.into_iter()
+    }
+}
+
+pub trait CrossoverMethod: Send + Sync {
+    fn try_crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError>;
+
+    /// Same as `try_crossover`, but panics if the parents don't match
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        self.try_crossover(rng, parent_a, parent_b)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CrossoverError {
+    LengthMismatch {
+        a: usize,
+        b: usize,
+    },
+
+    /// Parents don't match the topology given to a layer-aware method
+    LayerMismatch {
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl CrossoverError {
+    fn check(parent_a: &Chromosome, parent_b: &Chromosome) -> Result<(), Self> {
+        if parent_a.len() == parent_b.len() {
+            Ok(())
+        } else {
+            Err(Self::LengthMismatch {
+                a: parent_a.len(),
+                b: parent_b.len(),
+            })
+        }
+    }
+}
+
+impl fmt::Display for CrossoverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthMismatch { a, b } => {
+                write!(f, "parents have different lengths ({} vs {})", a, b)
+            }
+            Self::LayerMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "parents have {} gene(s), but their layers need {}",
+                    actual, expected
+                )
+            }
+        }
+    }
+}
+
+impl Error for CrossoverError {}
+
+pub struct UniformCrossover;
+
+impl CrossoverMethod for UniformCrossover {
+    fn try_crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        CrossoverError::check(parent_a, parent_b)?;
+
+        Ok(parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| if rng.gen_bool(0.5) { a } else { b })
+            .collect())
+    }
+}
+
+pub struct SinglePointCrossover;
+
+impl CrossoverMethod for SinglePointCrossover {
+    fn try_crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        CrossoverError::check(parent_a, parent_b)?;
+
+        // There's nowhere to cut a single gene, so just take it from A
+        if parent_a.len() < 2 {
+            return Ok(parent_a.clone());
+        }
+
+        let cut = rng.gen_range(1..parent_a.len());
+
+        Ok(parent_a
+            .iter()
+            .take(cut)
+            .chain(parent_b.iter().skip(cut))
+            .copied()
+            .collect())
+    }
+}
+
+/// Single-point crossover of neural networks' chromosomes that only ever
+/// cuts between layers, so that each layer gets inherited as a whole from
+/// one of the parents
+pub struct LayerAlignedCrossover {
+    points: Vec<usize>,
+    len: usize,
+}
+
+impl LayerAlignedCrossover {
+    pub fn new(topology: &NetworkTopology) -> Self {
+        Self {
+            points: Chromosome::crossover_points(topology),
+            len: topology.weight_count(),
+        }
+    }
+}
+
+impl CrossoverMethod for LayerAlignedCrossover {
+    fn try_crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        CrossoverError::check(parent_a, parent_b)?;
+
+        if parent_a.len() != self.len {
+            return Err(CrossoverError::LayerMismatch {
+                expected: self.len,
+                actual: parent_a.len(),
+            });
+        }
+
+        // A single layer can't be cut anywhere, so just take it from A
+        let Some(&cut) = self.points.choose(rng) else {
+            return Ok(parent_a.clone());
+        };
+
+        Ok(parent_a
+            .iter()
+            .take(cut)
+            .chain(parent_b.iter().skip(cut))
+            .copied()
+            .collect())
+    }
+}
+
+pub struct TwoPointCrossover;
+
+impl CrossoverMethod for TwoPointCrossover {
+    fn try_crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        CrossoverError::check(parent_a, parent_b)?;
+
+        // Two distinct cuts need at least two inner boundaries to pick from
+        if parent_a.len() < 3 {
+            return SinglePointCrossover.try_crossover(rng, parent_a, parent_b);
+        }
+
+        let cuts = rand::seq::index::sample(rng, parent_a.len() - 1, 2);
+        let (i, j) = (cuts.index(0) + 1, cuts.index(1) + 1);
+        let (i, j) = (i.min(j), i.max(j));
+
+        Ok(parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .enumerate()
+            .map(|(idx, (&a, &b))| if (i..j).contains(&idx) { b } else { a })
+            .collect())
+    }
+}
+
+pub struct ArithmeticCrossover {
+    alpha: Option<f32>,
+}
+
+impl ArithmeticCrossover {
+    /// When `alpha` is `None`, a fresh one is drawn for each crossover
+    pub fn new(alpha: Option<f32>) -> Self {
+        Self {
+            alpha: alpha.map(|alpha| alpha.clamp(0.0, 1.0)),
+        }
+    }
+}
+
+impl CrossoverMethod for ArithmeticCrossover {
+    fn try_crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        CrossoverError::check(parent_a, parent_b)?;
+
+        let alpha = self.alpha.unwrap_or_else(|| rng.gen_range(0.0..=1.0));
+
+        Ok(parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| alpha * a + (1.0 - alpha) * b)
+            .collect())
+    }
+}
+
+pub struct BlendCrossover {
+    alpha: f32,
+}
+
+impl BlendCrossover {
+    pub fn new(alpha: f32) -> Self {
+        assert!(alpha >= 0.0);
+
+        Self { alpha }
+    }
+}
+
+impl CrossoverMethod for BlendCrossover {
+    fn try_crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        CrossoverError::check(parent_a, parent_b)?;
+
+        Ok(parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| {
+                let d = (a - b).abs();
+                let min = a.min(b) - self.alpha * d;
+                let max = a.max(b) + self.alpha * d;
+
+                rng.gen_range(min..=max)
+            })
+            .collect())
+    }
+}
+
+pub struct SimulatedBinaryCrossover {
+    eta: f32,
+}
+
+impl SimulatedBinaryCrossover {
+    /// The bigger the `eta`, the closer children stay to their parents
+    pub fn new(eta: f32) -> Self {
+        assert!(eta >= 0.0);
+
+        Self { eta }
+    }
+}
+
+impl CrossoverMethod for SimulatedBinaryCrossover {
+    fn try_crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        CrossoverError::check(parent_a, parent_b)?;
+
+        Ok(parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| {
+                if a == b {
+                    return a;
+                }
+
+                let u: f32 = rng.gen();
+                let exp = 1.0 / (self.eta + 1.0);
+
+                let beta = if u <= 0.5 {
+                    (2.0 * u).powf(exp)
+                } else {
+                    (1.0 / (2.0 * (1.0 - u))).powf(exp)
+                };
+
+                0.5 * ((1.0 + beta) * a + (1.0 - beta) * b)
+            })
+            .collect())
+    }
+}
+
+/// Crossover that just clones parent A, turning the algorithm into a
+/// mutation-only one - handy for measuring how much crossover contributes.
+///
+/// Parents still have to be of the same length, for consistency with other
+/// crossovers.
+pub struct IdentityCrossover;
+
+impl CrossoverMethod for IdentityCrossover {
+    fn try_crossover(
+        &self,
+        _rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, CrossoverError> {
+        CrossoverError::check(parent_a, parent_b)?;
+
+        Ok(parent_a.clone())
+    }
+}
+
+pub trait MutationMethod: Send + Sync {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
+
+    /// Whether [`Self::on_generation()`] should get called, i.e. whether
+    /// population's diversity is worth computing
+    fn wants_diversity(&self) -> bool {
+        false
+    }
+
+    /// Called once per generation (if [`Self::wants_diversity()`]), before
+    /// any child gets mutated, with the current population's diversity - mean
+    /// variance of its genes
+    fn on_generation(&mut self, _population_diversity: f32) {
+        //
+    }
+
+    /// Called by [`GeneticAlgorithm::set_generation()`] with the zero-based
+    /// index of the generation that's about to be evolved
+    fn set_generation(&mut self, _generation: usize) {
+        //
+    }
+}
+
+/// Returns the standard deviation of each gene across the population,
+/// averaged over all genes - zero means that all the chromosomes are
+/// identical.
+///
+/// All individuals must carry chromosomes of the same length (e.g. ones
+/// whose `chromosome()` panics are not supported).
+pub fn population_diversity<I>(population: &[I]) -> f32
+where
+    I: Individual,
+{
+    mean(gene_moments(population).1.into_iter().map(f32::sqrt))
+}
+
+/// Like [`population_diversity()`], but averages gene variances instead
+/// of standard deviations; that's what's reported to mutation methods
+fn diversity<I>(population: &[I]) -> f32
+where
+    I: Individual,
+{
+    mean(gene_moments(population).1.into_iter())
+}
+
+/// Returns mean and variance of each gene across the population (both empty
+/// for an empty population)
+fn gene_moments<I>(population: &[I]) -> (Vec<f32>, Vec<f32>)
+where
+    I: Individual,
+{
+    let Some(first) = population.first() else {
+        return (Vec::new(), Vec::new());
+    };
+
+    let chromosomes = || population.iter().map(|individual| individual.chromosome());
+    let len = first.chromosome().len();
+    let n = population.len() as f32;
+
+    let mut means = vec![0.0; len];
+
+    for chromosome in chromosomes() {
+        assert_eq!(chromosome.len(), len, "chromosomes differ in length");
+
+        for (mean, gene) in means.iter_mut().zip(chromosome.iter()) {
+            *mean += gene / n;
+        }
+    }
+
+    let mut variances = vec![0.0; len];
+
+    for chromosome in chromosomes() {
+        for ((variance, mean), gene) in variances.iter_mut().zip(&means).zip(chromosome.iter()) {
+            *variance += (gene - mean).powi(2) / n;
+        }
+    }
+
+    (means, variances)
+}
+
+fn mean(values: impl ExactSizeIterator<Item = f32>) -> f32 {
+    let len = values.len();
+
+    if len == 0 {
+        0.0
+    } else {
+        values.sum::<f32>() / len as f32
+    }
+}
+
+pub struct GaussianMutation {
+    chance: f32,
+    coeff: f32,
+    bounds: Option<(f32, f32)>,
+}
+
+impl GaussianMutation {
+    pub fn new(chance: f32, coeff: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self {
+            chance,
+            coeff,
+            bounds: None,
+        }
+    }
+
+    /// Same as `new`, but clamps each mutated gene into `[min, max]`
+    pub fn with_bounds(chance: f32, coeff: f32, min: f32, max: f32) -> Self {
+        assert!(min <= max);
+
+        Self {
+            bounds: Some((min, max)),
+            ..Self::new(chance, coeff)
+        }
+    }
+}
+
+impl GaussianMutation {
+    fn mutate_gene(&self, rng: &mut dyn RngCore, gene: &mut f32) {
+        let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
+
+        if rng.gen_bool(self.chance as f64) {
+            *gene += sign * self.coeff * rng.gen::<f32>();
+
+            if let Some((min, max)) = self.bounds {
+                *gene = gene.clamp(min, max);
+            }
+        }
+    }
+}
+
+impl MutationMethod for GaussianMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        for gene in child.iter_mut() {
+            self.mutate_gene(rng, gene);
+        }
+    }
+}
+
+/// Gaussian mutation whose coefficient adapts to population's diversity:
+/// the lower the diversity, the stronger the mutation, so that a converging
+/// population gets shaken up.
+///
+/// At diversity of zero the coefficient is `max_coeff`, going linearly down
+/// to `min_coeff` as diversity reaches `target_diversity` (and staying there
+/// above it).
+pub struct AdaptiveGaussianMutation {
+    inner: GaussianMutation,
+    min_coeff: f32,
+    max_coeff: f32,
+    target_diversity: f32,
+}
+
+impl AdaptiveGaussianMutation {
+    pub fn new(chance: f32, min_coeff: f32, max_coeff: f32, target_diversity: f32) -> Self {
+        assert!(min_coeff <= max_coeff);
+        assert!(target_diversity > 0.0);
+
+        Self {
+            inner: GaussianMutation::new(chance, min_coeff),
+            min_coeff,
+            max_coeff,
+            target_diversity,
+        }
+    }
+
+    /// Coefficient that's currently applied to mutated genes
+    pub fn coeff(&self) -> f32 {
+        self.inner.coeff
+    }
+}
+
+impl MutationMethod for AdaptiveGaussianMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        self.inner.mutate(rng, child);
+    }
+
+    fn wants_diversity(&self) -> bool {
+        true
+    }
+
+    fn on_generation(&mut self, population_diversity: f32) {
+        let closeness = (population_diversity / self.target_diversity).clamp(0.0, 1.0);
+
+        self.inner.coeff = self.max_coeff - (self.max_coeff - self.min_coeff) * closeness;
+    }
+}
+
+/// Gaussian mutation annealed over generations: the coefficient starts at
+/// `coeff` and gets multiplied by `decay` with each generation, i.e.
+/// `coeff(gen) = coeff * decay^gen`.
+///
+/// The generation has to be provided through
+/// [`GeneticAlgorithm::set_generation()`].
+pub struct DecayingGaussianMutation {
+    inner: GaussianMutation,
+    initial_coeff: f32,
+    decay: f32,
+}
+
+impl DecayingGaussianMutation {
+    pub fn new(chance: f32, coeff: f32, decay: f32) -> Self {
+        assert!(decay >= 0.0);
+
+        Self {
+            inner: GaussianMutation::new(chance, coeff),
+            initial_coeff: coeff,
+            decay,
+        }
+    }
+
+    /// Coefficient that's currently applied to mutated genes
+    pub fn coeff(&self) -> f32 {
+        self.inner.coeff
+    }
+}
+
+impl MutationMethod for DecayingGaussianMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        self.inner.mutate(rng, child);
+    }
+
+    fn set_generation(&mut self, generation: usize) {
+        self.inner.coeff = self.initial_coeff * self.decay.powi(generation as i32);
+    }
+}
+
+pub struct UniformMutation {
+    chance: f32,
+    min: f32,
+    max: f32,
+}
+
+impl UniformMutation {
+    pub fn new(chance: f32, min: f32, max: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(min <= max);
+
+        Self { chance, min, max }
+    }
+}
+
+impl MutationMethod for UniformMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene = rng.gen_range(self.min..=self.max);
+            }
+        }
+    }
+}
+
+pub struct SwapMutation {
+    chance: f32,
+}
+
+impl SwapMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl MutationMethod for SwapMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        let len = child.len();
+
+        if len < 2 {
+            return;
+        }
+
+        for idx in 0..len {
+            if rng.gen_bool(self.chance as f64) {
+                // Pick from all the *other* positions, so that a swap
+                // always actually moves the gene
+                let other = (idx + rng.gen_range(1..len)) % len;
+
+                child.genes.swap(idx, other);
+            }
+        }
+    }
+}
+
+/// Mutation that doesn't draw anything from the rng - it walks through the
+/// script, adding consecutive perturbations to consecutive genes of
+/// consecutive children (i.e. the first child gets the first `len`
+/// perturbations, the second one gets the next `len` and so on), which comes
+/// handy for writing exact-value tests.
+///
+/// Panics once the script runs out of perturbations.
+#[derive(Debug)]
+pub struct ScriptedMutation {
+    perturbations: Vec<f32>,
+    cursor: AtomicUsize,
+}
+
+impl ScriptedMutation {
+    pub fn new(perturbations: impl IntoIterator<Item = f32>) -> Self {
+        Self {
+            perturbations: perturbations.into_iter().collect(),
+            cursor: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl Clone for ScriptedMutation {
+    fn clone(&self) -> Self {
+        Self {
+            perturbations: self.perturbations.clone(),
+            cursor: AtomicUsize::new(self.cursor.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl MutationMethod for ScriptedMutation {
+    fn mutate(&self, _rng: &mut dyn RngCore, child: &mut Chromosome) {
+        let start = self.cursor.fetch_add(child.len(), Ordering::Relaxed);
+
+        let perturbations = self
+            .perturbations
+            .get(start..start + child.len())
+            .unwrap_or_else(|| {
+                panic!(
+                    "script ran out of perturbations: got {}, but this child needs {}..{}",
+                    self.perturbations.len(),
+                    start,
+                    start + child.len(),
+                )
+            });
+
+        for (gene, perturbation) in child.iter_mut().zip(perturbations) {
+            *gene += perturbation;
+        }
+    }
+}
+
+/// Applies another mutation method only to some of the genes, e.g. to evolve
+/// just a part of a structured chromosome; `mask[i] == true` freezes the
+/// `i`-th gene
+pub struct MaskedMutation {
+    inner: Box<dyn MutationMethod>,
+    mask: Vec<bool>,
+}
+
+impl MaskedMutation {
+    pub fn new(inner: impl MutationMethod + 'static, mask: Vec<bool>) -> Self {
+        Self {
+            inner: Box::new(inner),
+            mask,
+        }
+    }
+}
+
+impl MutationMethod for MaskedMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        assert_eq!(
+            child.len(),
+            self.mask.len(),
+            "mask doesn't match chromosome's length"
+        );
+
+        let original = child.clone();
+
+        self.inner.mutate(rng, child);
+
+        for ((gene, original), &masked) in child.iter_mut().zip(original).zip(&self.mask) {
+            if masked {
+                *gene = original;
+            }
+        }
+    }
+
+    fn wants_diversity(&self) -> bool {
+        self.inner.wants_diversity()
+    }
+
+    fn on_generation(&mut self, population_diversity: f32) {
+        self.inner.on_generation(population_diversity);
+    }
+
+    fn set_generation(&mut self, generation: usize) {
+        self.inner.set_generation(generation);
+    }
+}
+
+/// Mutates biases and weights of a neural network's chromosome separately,
+/// so that e.g. biases can get nudged more gently than weights
+pub struct SegmentedMutation {
+    biases: GaussianMutation,
+    weights: GaussianMutation,
+
+    /// `is_bias[i]` tells whether the `i`-th gene is a bias
+    is_bias: Vec<bool>,
+}
+
+impl SegmentedMutation {
+    pub fn new(
+        topology: &NetworkTopology,
+        biases: GaussianMutation,
+        weights: GaussianMutation,
+    ) -> Self {
+        let mut is_bias = vec![false; topology.weight_count()];
+
+        for idx in topology.bias_indices() {
+            is_bias[idx] = true;
+        }
+
+        Self {
+            biases,
+            weights,
+            is_bias,
+        }
+    }
+}
+
+impl MutationMethod for SegmentedMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        assert_eq!(
+            child.len(),
+            self.is_bias.len(),
+            "layers don't match chromosome's length"
+        );
+
+        for (gene, &is_bias) in child.iter_mut().zip(&self.is_bias) {
+            if is_bias {
+                self.biases.mutate_gene(rng, gene);
+            } else {
+                self.weights.mutate_gene(rng, gene);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Statistics {
+    min_fitness: f32,
+    max_fitness: f32,
+    avg_fitness: f32,
+    median_fitness: f32,
+    gene_means: Option<Vec<f32>>,
+    gene_variances: Option<Vec<f32>>,
+}
+
+impl Statistics {
+    /// Computes statistics of given (non-empty) population
+    pub fn new<I>(population: &[I]) -> Self
+    where
+        I: Individual,
+    {
+        assert!(!population.is_empty());
+
+        let mut fitnesses: Vec<_> = population
+            .iter()
+            .map(|individual| individual.fitness())
+            .collect();
+
+        fitnesses.sort_by(|a, b| a.total_cmp(b));
+
+        let len = fitnesses.len();
+
+        let median_fitness = if len % 2 == 0 {
+            (fitnesses[len / 2 - 1] + fitnesses[len / 2]) / 2.0
+        } else {
+            fitnesses[len / 2]
+        };
+
+        Self {
+            min_fitness: fitnesses[0],
+            max_fitness: fitnesses[len - 1],
+            avg_fitness: fitnesses.iter().sum::<f32>() / (len as f32),
+            median_fitness,
+            gene_means: None,
+            gene_variances: None,
+        }
+    }
+
+    /// Adds mean & variance of each gene; all the chromosomes must be of the
+    /// same length
+    fn with_genes<I>(mut self, population: &[I]) -> Self
+    where
+        I: Individual,
+    {
+        let (means, variances) = gene_moments(population);
+
+        self.gene_means = Some(means);
+        self.gene_variances = Some(variances);
+        self
+    }
+
+    pub fn min_fitness(&self) -> f32 {
+        self.min_fitness
+    }
+
+    pub fn max_fitness(&self) -> f32 {
+        self.max_fitness
+    }
+
+    pub fn avg_fitness(&self) -> f32 {
+        self.avg_fitness
+    }
+
+    pub fn median_fitness(&self) -> f32 {
+        self.median_fitness
+    }
+
+    /// Mean of each gene across the population, if enabled through
+    /// [`GeneticAlgorithm::with_gene_statistics()`]
+    pub fn gene_means(&self) -> Option<&[f32]> {
+        self.gene_means.as_deref()
+    }
+
+    /// Variance of each gene across the population, if enabled through
+    /// [`GeneticAlgorithm::with_gene_statistics()`]
+    pub fn gene_variances(&self) -> Option<&[f32]> {
+        self.gene_variances.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::BTreeMap;
+    use std::iter::FromIterator;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestIndividual {
+        WithChromosome { chromosome: Chromosome },
+        WithFitness { fitness: f32 },
+    }
+
+    impl TestIndividual {
+        fn new(fitness: f32) -> Self {
+            Self::WithFitness { fitness }
+        }
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            TestIndividual::WithChromosome { chromosome }
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            match self {
+                Self::WithChromosome { chromosome } => chromosome,
+                Self::WithFitness { .. } => {
+                    panic!("not supported for TestIndividual::WithFitness")
+                }
+            }
+        }
+
+        fn fitness(&self) -> f32 {
+            match self {
+                Self::WithChromosome { chromosome } => chromosome.iter().sum(),
+                Self::WithFitness { fitness } => *fitness,
+            }
+        }
+    }
+
+    fn topology(neurons: &[usize]) -> NetworkTopology {
+        NetworkTopology::new(
+            neurons
+                .iter()
+                .map(|&neurons| lib_neural_network::LayerTopology::new(neurons))
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    impl PartialEq for Chromosome {
+        fn eq(&self, other: &Self) -> bool {
+            approx::relative_eq!(self.genes.as_slice(), other.genes.as_slice())
+        }
+    }
+
+    #[test]
+    fn genetic_algorithm() {
+        fn individual(genes: &[f32]) -> TestIndividual {
+            TestIndividual::create(genes.iter().cloned().collect())
+        }
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        let mut population = vec![
+            individual(&[0.0, 0.0, 0.0]),
+            individual(&[1.0, 1.0, 1.0]),
+            individual(&[1.0, 2.0, 1.0]),
+            individual(&[1.0, 2.0, 4.0]),
+        ];
+
+        for _ in 0..10 {
+            population = ga.evolve(&mut rng, &population).0;
+        }
+
+        let expected_population = vec![
+            individual(&[1.1013001, 1.6719795, 3.2955947]),
+            individual(&[1.3124838, 1.70271, 3.2955947]),
+            individual(&[1.7598352, 1.70271, 3.3595667]),
+            individual(&[1.3124838, 0.9894245, 3.270933]),
+        ];
+
+        assert_eq!(population, expected_population);
+    }
+
+    #[test]
+    fn try_evolve_given_empty_population() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        let population: Vec<TestIndividual> = Vec::new();
+
+        assert_eq!(
+            ga.try_evolve(&mut rng, &population).err(),
+            Some(EvolveError::EmptyPopulation)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "got an empty population")]
+    fn evolve_given_empty_population() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        ga.evolve::<TestIndividual>(&mut rng, &[]);
+    }
+
+    #[test]
+    fn evolve_detailed() {
+        fn individual(genes: &[f32]) -> TestIndividual {
+            TestIndividual::create(genes.iter().cloned().collect())
+        }
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        let population = vec![
+            individual(&[0.0, 0.0, 0.0]),
+            individual(&[1.0, 1.0, 1.0]),
+            individual(&[1.0, 2.0, 1.0]),
+            individual(&[1.0, 2.0, 4.0]),
+        ];
+
+        let mut rng_a = ChaCha8Rng::from_seed(Default::default());
+        let mut rng_b = ChaCha8Rng::from_seed(Default::default());
+
+        let evolution = ga.evolve_detailed(&mut rng_a, &population);
+        let (expected_population, _) = ga.evolve(&mut rng_b, &population);
+
+        assert_eq!(evolution.population, expected_population);
+
+        assert_eq!(
+            evolution.parent_fitnesses,
+            vec![(3.0, 7.0), (7.0, 7.0), (7.0, 4.0), (7.0, 3.0)]
+        );
+    }
+
+    #[test]
+    fn given_zero_crossover_chance_children_copy_parent_a() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover,
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_crossover_chance(0.0);
+
+        let population: Vec<_> = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]
+            .iter()
+            .map(|genes| TestIndividual::create(genes.iter().cloned().collect()))
+            .collect();
+
+        let evolution = ga.evolve_detailed(&mut rng, &population);
+
+        for (child, (parent_a_fitness, _)) in
+            evolution.population.iter().zip(&evolution.parent_fitnesses)
+        {
+            let parent_a = population
+                .iter()
+                .find(|individual| individual.fitness() == *parent_a_fitness)
+                .unwrap();
+
+            assert_eq!(child, parent_a);
+        }
+    }
+
+    #[test]
+    fn builder() {
+        let mut rng_a = ChaCha8Rng::from_seed(Default::default());
+        let mut rng_b = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga_a = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_elite_count(1);
+
+        let mut ga_b = GeneticAlgorithmBuilder::new()
+            .mutation(GaussianMutation::new(0.5, 0.5))
+            .elite_count(1)
+            .build();
+
+        let population: Vec<_> = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]
+            .iter()
+            .map(|genes| TestIndividual::create(genes.iter().cloned().collect()))
+            .collect();
+
+        assert_eq!(
+            ga_a.evolve(&mut rng_a, &population).0,
+            ga_b.evolve(&mut rng_b, &population).0
+        );
+    }
+
+    #[test]
+    fn gene_bounds() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithmBuilder::new()
+            .selection(RankSelection::new(1.5))
+            .crossover(ArithmeticCrossover::new(None))
+            .mutation(GaussianMutation::new(1.0, 2.0))
+            .gene_bounds(-1.0, 1.0)
+            .build();
+
+        let mut population: Vec<_> = (0..10)
+            .map(|_| TestIndividual::create((0..8).map(|_| rng.gen_range(-1.0..1.0)).collect()))
+            .collect();
+
+        for _ in 0..50 {
+            population = ga.evolve(&mut rng, &population).0;
+
+            for individual in &population {
+                assert!(individual
+                    .chromosome()
+                    .iter()
+                    .all(|gene| (-1.0..=1.0).contains(gene)));
+            }
+        }
+    }
+
+    #[test]
+    fn island_model_migrates_to_neighbor() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithmBuilder::new()
+            .selection(RankSelection::new(1.5))
+            .mutation(GaussianMutation::new(0.5, 0.1))
+            .elite_count(1)
+            .build();
+
+        let champion = TestIndividual::create(vec![10.0, 10.0, 10.0].into_iter().collect());
+
+        let island = |champion: Option<&TestIndividual>| {
+            let mut island: Vec<_> = (0..4)
+                .map(|_| TestIndividual::create(vec![1.0, 1.0, 1.0].into_iter().collect()))
+                .collect();
+
+            if let Some(champion) = champion {
+                island[2] = champion.clone();
+            }
+
+            island
+        };
+
+        let mut model = IslandModel::new(ga, vec![island(Some(&champion)), island(None)], 2, 1);
+
+        model.evolve(&mut rng);
+
+        assert!(model.islands()[0].contains(&champion));
+        assert!(!model.islands()[1].contains(&champion));
+
+        model.evolve(&mut rng);
+
+        assert_eq!(model.generation(), 2);
+        assert!(model.islands()[0].contains(&champion));
+        assert!(model.islands()[1].contains(&champion));
+    }
+
+    mod differential_evolution {
+        use super::*;
+
+        /// Fitness peaks at zero, with all genes being equal to one
+        struct Quadratic {
+            chromosome: Chromosome,
+        }
+
+        impl Individual for Quadratic {
+            fn create(chromosome: Chromosome) -> Self {
+                Self { chromosome }
+            }
+
+            fn chromosome(&self) -> &Chromosome {
+                &self.chromosome
+            }
+
+            fn fitness(&self) -> f32 {
+                -self
+                    .chromosome
+                    .iter()
+                    .map(|gene| (gene - 1.0).powi(2))
+                    .sum::<f32>()
+            }
+        }
+
+        fn best(population: &[Quadratic]) -> f32 {
+            population
+                .iter()
+                .map(Quadratic::fitness)
+                .fold(f32::NEG_INFINITY, f32::max)
+        }
+
+        #[test]
+        fn converges_toward_optimum() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut de = DifferentialEvolution::new(0.8, 0.9);
+
+            let mut population: Vec<_> = (0..20)
+                .map(|_| Quadratic::create((0..5).map(|_| rng.gen_range(-5.0..5.0)).collect()))
+                .collect();
+
+            let initial = best(&population);
+
+            for _ in 0..200 {
+                population = de.evolve(&mut rng, &population).0;
+            }
+
+            // The last children haven't been through selection yet, so let's
+            // look at the survivors
+            let survivors = de.targets.iter().map(|(_, fitness)| *fitness);
+            let survivor_best = survivors.fold(f32::NEG_INFINITY, f32::max);
+
+            assert!(initial < -1.0, "{}", initial);
+            assert!(survivor_best > -1e-3, "{}", survivor_best);
+        }
+
+        #[test]
+        fn selection_never_loses_fitter_target() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut de = DifferentialEvolution::new(0.5, 0.5);
+
+            let mut population: Vec<_> = (0..10)
+                .map(|_| Quadratic::create((0..3).map(|_| rng.gen_range(-5.0..5.0)).collect()))
+                .collect();
+
+            let mut previous = f32::NEG_INFINITY;
+
+            for _ in 0..20 {
+                population = de.evolve(&mut rng, &population).0;
+
+                let survivor_best = de
+                    .targets
+                    .iter()
+                    .map(|(_, fitness)| *fitness)
+                    .fold(f32::NEG_INFINITY, f32::max);
+
+                assert!(survivor_best >= previous);
+                previous = survivor_best;
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "got less than 4 individuals")]
+        fn panics_given_too_small_population() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let population: Vec<_> = (0..3)
+                .map(|_| Quadratic::create(vec![0.0].into_iter().collect()))
+                .collect();
+
+            DifferentialEvolution::new(0.5, 0.5).evolve(&mut rng, &population);
+        }
+    }
+
+    #[test]
+    fn repair() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithmBuilder::new()
+            .mutation(GaussianMutation::new(1.0, 0.5))
+            .repair(|chromosome| {
+                if let Some(gene) = chromosome.iter_mut().next() {
+                    *gene = 0.0;
+                }
+            })
+            .build();
+
+        let mut population: Vec<_> = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]
+            .iter()
+            .map(|genes| TestIndividual::create(genes.iter().cloned().collect()))
+            .collect();
+
+        for _ in 0..10 {
+            population = ga.evolve(&mut rng, &population).0;
+
+            for individual in &population {
+                assert_eq!(individual.chromosome()[0], 0.0);
+            }
+        }
+    }
+
+    mod evolve_seeded {
+        use super::*;
+
+        fn ga() -> GeneticAlgorithm<RouletteWheelSelection> {
+            GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover,
+                GaussianMutation::new(0.5, 0.5),
+            )
+            .with_elite_count(1)
+        }
+
+        fn population() -> Vec<TestIndividual> {
+            (0..50)
+                .map(|n| TestIndividual::create((0..10).map(|m| (n * m) as f32).collect()))
+                .collect()
+        }
+
+        #[test]
+        fn is_deterministic() {
+            let mut ga = ga();
+
+            assert_eq!(
+                ga.evolve_seeded(1234, &population()).0,
+                ga.evolve_seeded(1234, &population()).0
+            );
+
+            assert_ne!(
+                ga.evolve_seeded(1234, &population()).0,
+                ga.evolve_seeded(4321, &population()).0
+            );
+        }
+
+        #[cfg(feature = "rayon")]
+        #[test]
+        fn serial_and_parallel_outputs_are_equal() {
+            let mut ga = ga();
+            let mut serial = population();
+            let mut parallel = population();
+
+            for generation in 0..10 {
+                serial = ga.evolve_seeded(generation, &serial).0;
+                parallel = ga.par_evolve_seeded(generation, &parallel).0;
+            }
+
+            assert_eq!(serial, parallel);
+        }
+
+        #[test]
+        fn draws_parents_all_at_once() {
+            struct BatchOnlySelection;
+
+            impl SelectionMethod for BatchOnlySelection {
+                fn select<'a, I>(&self, _: &mut dyn RngCore, _: &'a [I]) -> &'a I
+                where
+                    I: Individual,
+                {
+                    panic!("parents should get drawn through select_many()");
+                }
+
+                fn select_many<'a, I>(
+                    &self,
+                    _: &mut dyn RngCore,
+                    population: &'a [I],
+                    n: usize,
+                ) -> Vec<&'a I>
+                where
+                    I: Individual,
+                {
+                    population.iter().cycle().take(n).collect()
+                }
+            }
+
+            let mut ga = GeneticAlgorithm::new(
+                BatchOnlySelection,
+                UniformCrossover,
+                GaussianMutation::new(0.0, 0.0),
+            )
+            .with_crossover_chance(0.0);
+
+            let (population, _) = ga.evolve_seeded(1234, &population());
+
+            // Without crossover & mutation, each child copies its first
+            // parent, i.e. every other individual of the original population
+            let expected: Vec<_> = self::population()
+                .into_iter()
+                .cycle()
+                .step_by(2)
+                .take(50)
+                .collect();
+
+            assert_eq!(population, expected);
+        }
+    }
+
+    mod elitism {
+        use super::*;
+
+        fn individual(genes: &[f32]) -> TestIndividual {
+            TestIndividual::create(genes.iter().cloned().collect())
+        }
+
+        fn population() -> Vec<TestIndividual> {
+            vec![
+                individual(&[0.0, 0.0, 0.0]),
+                individual(&[1.0, 1.0, 1.0]),
+                individual(&[1.0, 2.0, 1.0]),
+                individual(&[1.0, 2.0, 4.0]),
+            ]
+        }
+
+        fn max_fitness(population: &[TestIndividual]) -> f32 {
+            population
+                .iter()
+                .map(|individual| individual.fitness())
+                .fold(f32::MIN, f32::max)
+        }
+
+        #[test]
+        fn keeps_max_fitness_from_decreasing() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let mut ga = GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover,
+                GaussianMutation::new(0.5, 2.0),
+            )
+            .with_elite_count(1);
+
+            let mut population = population();
+            let mut prev_max_fitness = max_fitness(&population);
+
+            for _ in 0..50 {
+                population = ga.evolve(&mut rng, &population).0;
+
+                let curr_max_fitness = max_fitness(&population);
+
+                assert!(curr_max_fitness >= prev_max_fitness);
+                prev_max_fitness = curr_max_fitness;
+            }
+        }
+
+        #[test]
+        fn given_zero_elites_behaves_like_no_elitism() {
+            let mut rng_a = ChaCha8Rng::from_seed(Default::default());
+            let mut rng_b = ChaCha8Rng::from_seed(Default::default());
+
+            let mut ga_a = GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover,
+                GaussianMutation::new(0.5, 0.5),
+            );
+
+            let mut ga_b = GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover,
+                GaussianMutation::new(0.5, 0.5),
+            )
+            .with_elite_count(0);
+
+            let population_a = ga_a.evolve(&mut rng_a, &population()).0;
+            let population_b = ga_b.evolve(&mut rng_b, &population()).0;
+
+            assert_eq!(population_a, population_b);
+        }
+
+        #[test]
+        fn given_elites_for_everyone_clones_the_whole_population() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let mut ga = GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover,
+                GaussianMutation::new(0.5, 0.5),
+            )
+            .with_elite_count(10);
+
+            let actual = ga.evolve(&mut rng, &population()).0;
+
+            let expected = vec![
+                individual(&[1.0, 2.0, 4.0]),
+                individual(&[1.0, 2.0, 1.0]),
+                individual(&[1.0, 1.0, 1.0]),
+                individual(&[0.0, 0.0, 0.0]),
+            ];
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn roulette_wheel_selection() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = vec![
+            TestIndividual::new(2.0),
+            TestIndividual::new(1.0),
+            TestIndividual::new(4.0),
+            TestIndividual::new(3.0),
+        ];
+
+        let mut actual_histogram = BTreeMap::new();
+
+        for _ in 0..1000 {
+            let fitness = RouletteWheelSelection::new()
+                .select(&mut rng, &population)
+                .fitness() as i32;
+
+            *actual_histogram.entry(fitness).or_insert(0) += 1;
+        }
+
+        let expected_histogram = BTreeMap::from_iter([(1, 98), (2, 202), (3, 278), (4, 422)]);
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    mod chromosome {
+        use super::*;
+
+        #[test]
+        fn new() {
+            let chromosome = Chromosome::new(vec![1.0, 2.0, 3.0]);
+
+            assert_eq!(chromosome.len(), 3);
+            assert!(!chromosome.is_empty());
+            assert_eq!(chromosome.as_slice(), &[1.0, 2.0, 3.0]);
+        }
+
+        #[test]
+        fn is_empty() {
+            assert!(Chromosome::new(vec![]).is_empty());
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn serde_round_trip() {
+            let chromosome = Chromosome::new(vec![1.0, -2.5, 3.25]);
+
+            let json = serde_json::to_string(&chromosome).unwrap();
+            assert_eq!(json, "[1.0,-2.5,3.25]");
+
+            let actual: Chromosome = serde_json::from_str(&json).unwrap();
+            assert_eq!(actual, chromosome);
+        }
+
+        #[test]
+        fn try_new() {
+            assert_eq!(
+                Chromosome::try_new(vec![1.0, 2.0]),
+                Ok(Chromosome::new(vec![1.0, 2.0]))
+            );
+
+            assert_eq!(
+                Chromosome::try_new(vec![1.0, f32::NAN]),
+                Err(ChromosomeError::NanGene { index: 1 })
+            );
+        }
+
+        #[test]
+        fn euclidean_distance() {
+            let a = Chromosome::new(vec![1.0, 2.0, 3.0]);
+            let b = Chromosome::new(vec![4.0, 6.0, 3.0]);
+
+            assert_eq!(a.euclidean_distance(&a), 0.0);
+            assert_eq!(a.euclidean_distance(&b), 5.0);
+        }
+
+        #[test]
+        #[should_panic]
+        fn euclidean_distance_of_different_lengths() {
+            Chromosome::new(vec![1.0]).euclidean_distance(&Chromosome::new(vec![1.0, 2.0]));
+        }
+
+        #[test]
+        fn mean() {
+            let actual = Chromosome::mean(&[
+                Chromosome::new(vec![1.0, -2.0, 3.0]),
+                Chromosome::new(vec![3.0, 2.0, 4.0]),
+            ]);
+
+            assert_eq!(actual, Chromosome::new(vec![2.0, 0.0, 3.5]));
+        }
+
+        #[test]
+        #[should_panic]
+        fn mean_of_different_lengths() {
+            Chromosome::mean(&[Chromosome::new(vec![1.0]), Chromosome::new(vec![1.0, 2.0])]);
+        }
+    }
+
+    mod fitness_scaling {
+        use super::*;
+        use approx::assert_relative_eq;
+
+        fn histogram(selection: RouletteWheelSelection) -> BTreeMap<i32, i32> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = vec![TestIndividual::new(1.0), TestIndividual::new(2.0)];
+            let mut histogram = BTreeMap::new();
+
+            for _ in 0..1000 {
+                let fitness = selection.select(&mut rng, &population).fitness() as i32;
+                *histogram.entry(fitness).or_insert(0) += 1;
+            }
+
+            histogram
+        }
+
+        #[test]
+        fn power_scaling_widens_selection_gap() {
+            let unscaled = histogram(RouletteWheelSelection::new());
+            let scaled = histogram(RouletteWheelSelection::with_scaling(PowerScaling::new(2.0)));
+
+            assert_eq!(unscaled, BTreeMap::from_iter([(1, 327), (2, 673)]));
+            assert_eq!(scaled, BTreeMap::from_iter([(1, 202), (2, 798)]));
+        }
+
+        #[test]
+        fn linear_scaling() {
+            assert_relative_eq!(LinearScaling::new(2.0, 1.0).scale(3.0), 7.0);
+        }
+
+        #[test]
+        fn sigmoid_scaling() {
+            let scaling = SigmoidScaling::new(1.0, 5.0);
+
+            assert_relative_eq!(scaling.scale(5.0), 0.5);
+            assert!(scaling.scale(0.0) < 0.01);
+            assert!(scaling.scale(10.0) > 0.99);
+        }
+    }
+
+    mod statistics {
+        use super::*;
+        use approx::assert_relative_eq;
+
+        #[test]
+        fn given_odd_population() {
+            let population = vec![
+                TestIndividual::new(30.0),
+                TestIndividual::new(10.0),
+                TestIndividual::new(20.0),
+                TestIndividual::new(40.0),
+                TestIndividual::new(50.0),
+            ];
+
+            let stats = Statistics::new(&population);
+
+            assert_relative_eq!(stats.min_fitness(), 10.0);
+            assert_relative_eq!(stats.max_fitness(), 50.0);
+            assert_relative_eq!(stats.avg_fitness(), 30.0);
+            assert_relative_eq!(stats.median_fitness(), 30.0);
+        }
+
+        #[test]
+        fn given_even_population() {
+            let population = vec![
+                TestIndividual::new(30.0),
+                TestIndividual::new(10.0),
+                TestIndividual::new(20.0),
+                TestIndividual::new(60.0),
+            ];
+
+            let stats = Statistics::new(&population);
+
+            assert_relative_eq!(stats.min_fitness(), 10.0);
+            assert_relative_eq!(stats.max_fitness(), 60.0);
+            assert_relative_eq!(stats.avg_fitness(), 30.0);
+            assert_relative_eq!(stats.median_fitness(), 25.0);
+        }
+
+        #[test]
+        fn gene_statistics() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let population: Vec<_> = [[1.0, 2.0, 3.0], [3.0, 2.0, -3.0], [5.0, 2.0, 0.0]]
+                .iter()
+                .map(|genes| TestIndividual::create(genes.iter().cloned().collect()))
+                .collect();
+
+            let (_, stats) = GeneticAlgorithmBuilder::new()
+                .gene_statistics(true)
+                .build()
+                .evolve(&mut rng, &population);
+
+            assert_relative_eq!(stats.gene_means().unwrap(), [3.0, 2.0, 0.0].as_slice());
+
+            assert_relative_eq!(
+                stats.gene_variances().unwrap(),
+                [8.0 / 3.0, 0.0, 6.0].as_slice()
+            );
+        }
+
+        #[test]
+        fn gene_statistics_are_opt_in() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = vec![TestIndividual::create(Chromosome::new(vec![1.0, 2.0]))];
+
+            let (_, stats) = GeneticAlgorithmBuilder::new()
+                .build()
+                .evolve(&mut rng, &population);
+
+            assert_eq!(stats.gene_means(), None);
+            assert_eq!(stats.gene_variances(), None);
+        }
+    }
+
+    #[test]
+    fn roulette_wheel_selection_given_zero_fitness() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = vec![
+            TestIndividual::new(0.0),
+            TestIndividual::new(0.0),
+            TestIndividual::new(0.0),
+        ];
+
+        let individual = RouletteWheelSelection::new().select(&mut rng, &population);
+
+        assert_eq!(individual, &TestIndividual::new(0.0));
+    }
+
+    mod quantized_roulette_wheel_selection {
+        use super::*;
+
+        fn histogram(buckets: u32, population: &[TestIndividual]) -> BTreeMap<i32, i32> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut histogram = BTreeMap::new();
+
+            for individual in
+                RouletteWheelSelection::quantized(buckets).select_many(&mut rng, population, 1000)
+            {
+                *histogram.entry(individual.fitness() as i32).or_insert(0) += 1;
+            }
+
+            histogram
+        }
+
+        #[test]
+        fn is_reproducible() {
+            let population = vec![
+                TestIndividual::new(2.0),
+                TestIndividual::new(1.0),
+                TestIndividual::new(4.0),
+                TestIndividual::new(3.0),
+            ];
+
+            let actual = histogram(1000, &population);
+
+            assert_eq!(actual, histogram(1000, &population));
+
+            assert_eq!(
+                actual,
+                BTreeMap::from_iter([(1, 98), (2, 191), (3, 280), (4, 431)])
+            );
+        }
+
+        #[test]
+        fn coarse_buckets_drop_weak_individuals() {
+            let population = vec![TestIndividual::new(1.0), TestIndividual::new(4.0)];
+
+            assert_eq!(histogram(1, &population), BTreeMap::from_iter([(4, 1000)]));
+        }
+
+        #[test]
+        fn given_zero_fitness() {
+            let population = vec![TestIndividual::new(0.0), TestIndividual::new(0.0)];
+
+            assert_eq!(histogram(10, &population), BTreeMap::from_iter([(0, 1000)]));
+        }
+    }
+
+    #[test]
+    fn stochastic_universal_sampling() {
+        fn histogram(selected: Vec<&TestIndividual>) -> BTreeMap<i32, i32> {
+            let mut histogram = BTreeMap::new();
+
+            for individual in selected {
+                *histogram.entry(individual.fitness() as i32).or_insert(0) += 1;
+            }
+
+            histogram
+        }
+
+        let population = vec![
+            TestIndividual::new(2.0),
+            TestIndividual::new(1.0),
+            TestIndividual::new(4.0),
+            TestIndividual::new(3.0),
+        ];
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let sus = histogram(StochasticUniversalSampling.select_many(&mut rng, &population, 1000));
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let roulette =
+            histogram(RouletteWheelSelection::new().select_many(&mut rng, &population, 1000));
+
+        assert_eq!(
+            sus,
+            BTreeMap::from_iter([(1, 100), (2, 200), (3, 300), (4, 400)])
+        );
+        assert_eq!(
+            roulette,
+            BTreeMap::from_iter([(1, 98), (2, 202), (3, 278), (4, 422)])
+        );
+    }
+
+    #[test]
+    fn truncation_selection() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population: Vec<_> = (1..=8).map(|n| TestIndividual::new(n as f32)).collect();
+
+        let mut actual_histogram = BTreeMap::new();
+
+        for _ in 0..1000 {
+            let fitness = TruncationSelection::new(0.25)
+                .select(&mut rng, &population)
+                .fitness() as i32;
+
+            *actual_histogram.entry(fitness).or_insert(0) += 1;
+        }
+
+        // Only the top quarter (i.e. two best individuals) ever gets picked
+        let expected_histogram = BTreeMap::from_iter([(7, 521), (8, 479)]);
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    mod tournament_selection {
+        use super::*;
+
+        fn histogram(size: usize) -> BTreeMap<i32, i32> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let population = vec![
+                TestIndividual::new(2.0),
+                TestIndividual::new(1.0),
+                TestIndividual::new(4.0),
+                TestIndividual::new(3.0),
+            ];
+
+            let mut histogram = BTreeMap::new();
+
+            for _ in 0..1000 {
+                let fitness = TournamentSelection::new(size)
+                    .select(&mut rng, &population)
+                    .fitness() as i32;
+
+                *histogram.entry(fitness).or_insert(0) += 1;
+            }
+
+            histogram
+        }
+
+        #[test]
+        fn given_size_of_one_selects_at_random() {
+            let expected_histogram = BTreeMap::from_iter([(1, 262), (2, 246), (3, 238), (4, 254)]);
+
+            assert_eq!(histogram(1), expected_histogram);
+        }
+
+        #[test]
+        fn given_size_of_two_favors_the_fittest() {
+            // The weakest individual can never win a two-way tournament
+            let expected_histogram = BTreeMap::from_iter([(2, 144), (3, 339), (4, 517)]);
+
+            assert_eq!(histogram(2), expected_histogram);
+        }
+
+        #[test]
+        fn given_size_bigger_than_population_always_selects_the_fittest() {
+            let expected_histogram = BTreeMap::from_iter([(4, 1000)]);
+
+            assert_eq!(histogram(10), expected_histogram);
+        }
+    }
+
+    #[test]
+    fn rank_selection() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = vec![TestIndividual::new(1000.0), TestIndividual::new(1.0)];
+
+        let mut actual_histogram = BTreeMap::new();
+
+        for _ in 0..1000 {
+            let fitness = RankSelection::new(1.5)
+                .select(&mut rng, &population)
+                .fitness() as i32;
+
+            *actual_histogram.entry(fitness).or_insert(0) += 1;
+        }
+
+        // Fitnesses differ 1000x, but ranks are just 1 and 2, which gives
+        // a 1:3 ratio for this selective pressure
+        let expected_histogram = BTreeMap::from_iter([(1, 248), (1000, 752)]);
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn uniform_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = (0..=100).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (0..=100).map(|n| -n as f32).collect();
+
+        let child = UniformCrossover.crossover(&mut rng, &parent_a, &parent_b);
+
+        let diff_a = child.iter().zip(parent_a).filter(|(c, p)| *c != p).count();
+        let diff_b = child.iter().zip(parent_b).filter(|(c, p)| *c != p).count();
+
+        assert_eq!(diff_a, 49);
+        assert_eq!(diff_b, 51);
+    }
+
+    mod crossover_length_mismatch {
+        use super::*;
+        use test_case::test_case;
+
+        fn crossover(method: &dyn CrossoverMethod) -> Result<Chromosome, CrossoverError> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent_a: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+            let parent_b: Chromosome = vec![1.0, 2.0].into_iter().collect();
+
+            method.try_crossover(&mut rng, &parent_a, &parent_b)
+        }
+
+        #[test_case(&UniformCrossover ; "uniform")]
+        #[test_case(&SinglePointCrossover ; "single point")]
+        #[test_case(&TwoPointCrossover ; "two point")]
+        #[test_case(&ArithmeticCrossover::new(None) ; "arithmetic")]
+        #[test_case(&BlendCrossover::new(0.5) ; "blend")]
+        #[test_case(&SimulatedBinaryCrossover::new(2.0) ; "simulated binary")]
+        #[test_case(&IdentityCrossover ; "identity")]
+        fn yields_error(method: &dyn CrossoverMethod) {
+            assert_eq!(
+                crossover(method),
+                Err(CrossoverError::LengthMismatch { a: 3, b: 2 })
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "parents have different lengths (3 vs 2)")]
+        fn panics_through_crossover() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent_a: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+            let parent_b: Chromosome = vec![1.0, 2.0].into_iter().collect();
+
+            UniformCrossover.crossover(&mut rng, &parent_a, &parent_b);
+        }
+    }
+
+    mod single_point_crossover {
+        use super::*;
+
+        #[test]
+        fn takes_prefix_from_a_and_suffix_from_b() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent_a: Chromosome = (1..=100).map(|n| n as f32).collect();
+            let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
+
+            let child = SinglePointCrossover.crossover(&mut rng, &parent_a, &parent_b);
+
+            let cut = child
+                .iter()
+                .position(|gene| *gene < 0.0)
+                .expect("child should contain genes from parent B");
+
+            assert_eq!(cut, 63);
+            assert_eq!(&child.genes[..cut], &parent_a.genes[..cut]);
+            assert_eq!(&child.genes[cut..], &parent_b.genes[cut..]);
+        }
+
+        #[test]
+        fn given_single_gene_returns_parent_a() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent_a: Chromosome = vec![1.0].into_iter().collect();
+            let parent_b: Chromosome = vec![-1.0].into_iter().collect();
+
+            let child = SinglePointCrossover.crossover(&mut rng, &parent_a, &parent_b);
+
+            assert_eq!(child, parent_a);
+        }
+    }
+
+    mod layer_aligned_crossover {
+        use super::*;
+
+        const LAYERS: &[usize] = &[3, 4, 2, 2];
+
+        #[test]
+        fn crossover_points() {
+            assert_eq!(
+                Chromosome::crossover_points(&topology(LAYERS)),
+                vec![16, 26]
+            );
+
+            assert_eq!(
+                Chromosome::crossover_points(&topology(&[3, 2])),
+                Vec::<usize>::new()
+            );
+        }
+
+        #[test]
+        fn cuts_at_layer_boundaries() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent_a: Chromosome = (1..=32).map(|n| n as f32).collect();
+            let parent_b: Chromosome = (1..=32).map(|n| -n as f32).collect();
+            let mut cuts = BTreeMap::new();
+
+            for _ in 0..100 {
+                let child = LayerAlignedCrossover::new(&topology(LAYERS))
+                    .crossover(&mut rng, &parent_a, &parent_b);
+
+                let cut = child.iter().position(|gene| *gene < 0.0).unwrap();
+
+                assert_eq!(&child.genes[..cut], &parent_a.genes[..cut]);
+                assert_eq!(&child.genes[cut..], &parent_b.genes[cut..]);
+
+                *cuts.entry(cut).or_insert(0) += 1;
+            }
+
+            assert_eq!(cuts.keys().copied().collect::<Vec<_>>(), vec![16, 26]);
+        }
+
+        #[test]
+        fn given_single_layer_returns_parent_a() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent_a: Chromosome = vec![1.0; 8].into_iter().collect();
+            let parent_b: Chromosome = vec![-1.0; 8].into_iter().collect();
+
+            let child = LayerAlignedCrossover::new(&topology(&[3, 2]))
+                .crossover(&mut rng, &parent_a, &parent_b);
+
+            assert_eq!(child, parent_a);
+        }
+
+        #[test]
+        fn given_chromosomes_not_matching_layers_returns_error() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent_a: Chromosome = vec![1.0; 31].into_iter().collect();
+            let parent_b: Chromosome = vec![-1.0; 31].into_iter().collect();
+
+            let err = LayerAlignedCrossover::new(&topology(LAYERS))
+                .try_crossover(&mut rng, &parent_a, &parent_b)
+                .unwrap_err();
+
+            assert_eq!(
+                err,
+                CrossoverError::LayerMismatch {
+                    expected: 32,
+                    actual: 31
+                }
+            );
+        }
+    }
+
+    mod two_point_crossover {
+        use super::*;
+
+        fn genes_from_b(child: &Chromosome) -> Vec<usize> {
+            child
+                .iter()
+                .enumerate()
+                .filter(|(_, gene)| **gene < 0.0)
+                .map(|(idx, _)| idx)
+                .collect()
+        }
+
+        #[test]
+        fn swaps_middle_segment_from_b() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent_a: Chromosome = (1..=10).map(|n| n as f32).collect();
+            let parent_b: Chromosome = (1..=10).map(|n| -n as f32).collect();
+
+            let child = TwoPointCrossover.crossover(&mut rng, &parent_a, &parent_b);
+
+            assert_eq!(genes_from_b(&child), vec![2, 3, 4, 5, 6, 7]);
+        }
+
+        #[test]
+        fn given_two_genes_behaves_like_single_point() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent_a: Chromosome = vec![1.0, 2.0].into_iter().collect();
+            let parent_b: Chromosome = vec![-1.0, -2.0].into_iter().collect();
+
+            let child = TwoPointCrossover.crossover(&mut rng, &parent_a, &parent_b);
+
+            assert_eq!(genes_from_b(&child), vec![1]);
+        }
+    }
+
+    mod arithmetic_crossover {
+        use super::*;
+
+        fn parents() -> (Chromosome, Chromosome) {
+            let parent_a = (1..=10).map(|n| n as f32).collect();
+            let parent_b = (1..=10).map(|n| -2.0 * n as f32).collect();
+
+            (parent_a, parent_b)
+        }
+
+        #[test]
+        fn given_half_alpha_returns_midpoint() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents();
+
+            let child =
+                ArithmeticCrossover::new(Some(0.5)).crossover(&mut rng, &parent_a, &parent_b);
+            let expected: Chromosome = (1..=10).map(|n| -0.5 * n as f32).collect();
+
+            assert_eq!(child, expected);
+        }
+
+        #[test]
+        fn given_out_of_range_alpha_clamps_it() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents();
+
+            let child =
+                ArithmeticCrossover::new(Some(1.5)).crossover(&mut rng, &parent_a, &parent_b);
+
+            assert_eq!(child, parent_a);
+        }
+
+        #[test]
+        fn given_no_alpha_stays_between_parents() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (parent_a, parent_b) = parents();
+
+            let child = ArithmeticCrossover::new(None).crossover(&mut rng, &parent_a, &parent_b);
+
+            for ((c, a), b) in child.iter().zip(parent_a.iter()).zip(parent_b.iter()) {
+                assert!(c <= a && c >= b);
+            }
         }
     }
 
     #[test]
-    fn genetic_algorithm() {
-        fn individual(genes: &[f32]) -> TestIndividual {
-            TestIndividual::create(genes.iter().cloned().collect())
-        }
-
+    fn blend_crossover() {
         let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = (0..=100).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (0..=100).map(|n| -n as f32).collect();
 
-        let ga = GeneticAlgorithm::new(
-            RouletteWheelSelection,
-            UniformCrossover,
-            GaussianMutation::new(0.5, 0.5),
-        );
+        let child = BlendCrossover::new(0.5).crossover(&mut rng, &parent_a, &parent_b);
 
-        let mut population = vec![
-            individual(&[0.0, 0.0, 0.0]),
-            individual(&[1.0, 1.0, 1.0]),
-            individual(&[1.0, 2.0, 1.0]),
-            individual(&[1.0, 2.0, 4.0]),
-        ];
+        let mut escaped = 0;
 
-        for _ in 0..10 {
-            population = ga.evolve(&mut rng, &population);
-        }
+        for ((&c, &a), &b) in child.iter().zip(parent_a.iter()).zip(parent_b.iter()) {
+            let d = (a - b).abs();
 
-        let expected_population = vec![
-            individual(&[0.44769490, 2.0648358, 4.3058133]),
-            individual(&[1.21268670, 1.5538777, 2.8869110]),
-            individual(&[1.06176780, 2.2657390, 4.4287640]),
-            individual(&[0.95909685, 2.4618788, 4.0247330]),
-        ];
+            assert!(c >= a.min(b) - 0.5 * d);
+            assert!(c <= a.max(b) + 0.5 * d);
 
-        assert_eq!(population, expected_population);
+            if c < a.min(b) || c > a.max(b) {
+                escaped += 1;
+            }
+        }
+
+        // Some genes should've been drawn from outside the parents' range
+        assert_eq!(escaped, 39);
     }
 
-    #[test]
-    fn roulette_wheel_selection() {
-        let mut rng = ChaCha8Rng::from_seed(Default::default());
+    mod simulated_binary_crossover {
+        use super::*;
 
-        let population = vec![
-            TestIndividual::new(2.0),
-            TestIndividual::new(1.0),
-            TestIndividual::new(4.0),
-            TestIndividual::new(3.0),
-        ];
+        /// Returns how far (on average) children land from parent A,
+        /// relative to the distance between both parents
+        fn spread(eta: f32) -> f32 {
+            let parent_a: Chromosome = vec![1.0; 50].into_iter().collect();
+            let parent_b: Chromosome = vec![-1.0; 50].into_iter().collect();
 
-        let mut actual_histogram = BTreeMap::new();
+            let mut sum = 0.0;
+            let mut count = 0;
 
-        for _ in 0..1000 {
-            let fitness = RouletteWheelSelection
-                .select(&mut rng, &population)
-                .fitness() as i32;
+            for seed in 0..100 {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
 
-            *actual_histogram.entry(fitness).or_insert(0) += 1;
+                let child =
+                    SimulatedBinaryCrossover::new(eta).crossover(&mut rng, &parent_a, &parent_b);
+
+                for (c, a) in child.iter().zip(parent_a.iter()) {
+                    sum += (c - a).abs() / 2.0;
+                    count += 1;
+                }
+            }
+
+            sum / (count as f32)
         }
 
-        let expected_histogram = BTreeMap::from_iter([(1, 98), (2, 202), (3, 278), (4, 422)]);
+        #[test]
+        fn given_large_eta_children_stay_close_to_parents() {
+            assert!(spread(100.0) < 0.01);
+        }
 
-        assert_eq!(actual_histogram, expected_histogram);
+        #[test]
+        fn given_small_eta_children_spread_widely() {
+            assert!(spread(0.1) > 1.0);
+            assert!(spread(0.1) > 10.0 * spread(100.0));
+        }
+
+        #[test]
+        fn given_identical_parents_returns_parent() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent: Chromosome = (1..=10).map(|n| n as f32).collect();
+
+            let child = SimulatedBinaryCrossover::new(2.0).crossover(&mut rng, &parent, &parent);
+
+            assert_eq!(child, parent);
+        }
     }
 
-    #[test]
-    fn uniform_crossover() {
-        let mut rng = ChaCha8Rng::from_seed(Default::default());
-        let parent_a: Chromosome = (0..=100).map(|n| n as f32).collect();
-        let parent_b: Chromosome = (0..=100).map(|n| -n as f32).collect();
+    mod identity_crossover {
+        use super::*;
 
-        let child = UniformCrossover.crossover(&mut rng, &parent_a, &parent_b);
+        #[test]
+        fn child_equals_parent_a() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let parent_a: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+            let parent_b: Chromosome = vec![4.0, 5.0, 6.0].into_iter().collect();
 
-        let diff_a = child.iter().zip(parent_a).filter(|(c, p)| *c != p).count();
-        let diff_b = child.iter().zip(parent_b).filter(|(c, p)| *c != p).count();
+            let child = IdentityCrossover.crossover(&mut rng, &parent_a, &parent_b);
 
-        assert_eq!(diff_a, 49);
-        assert_eq!(diff_b, 51);
+            assert_eq!(child.genes, parent_a.genes);
+        }
     }
 
     mod gaussian_mutation {
@@ -435,4 +3391,470 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn bounded_gaussian_mutation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child: Chromosome = vec![0.0; 10].into_iter().collect();
+        let mutation = GaussianMutation::with_bounds(1.0, 0.5, -0.1, 0.1);
+
+        for _ in 0..1000 {
+            mutation.mutate(&mut rng, &mut child);
+
+            for gene in child.iter() {
+                assert!((-0.1..=0.1).contains(gene));
+            }
+        }
+    }
+
+    mod population_diversity {
+        use super::*;
+
+        fn population(chromosomes: &[[f32; 3]]) -> Vec<TestIndividual> {
+            chromosomes
+                .iter()
+                .map(|genes| TestIndividual::create(genes.iter().copied().collect()))
+                .collect()
+        }
+
+        #[test]
+        fn identical_chromosomes() {
+            let population = population(&[[1.0, 2.0, 3.0]; 4]);
+
+            assert_eq!(population_diversity(&population), 0.0);
+        }
+
+        #[test]
+        fn spread_out_chromosomes() {
+            let close = population(&[[1.0, 2.0, 3.0], [1.1, 2.1, 3.1]]);
+            let far = population(&[[1.0, 2.0, 3.0], [-5.0, 8.0, 0.0]]);
+
+            assert!(population_diversity(&close) > 0.0);
+            assert!(population_diversity(&far) > population_diversity(&close));
+
+            // Per gene deviations are 3.0, 3.0 and 1.5
+            assert_eq!(population_diversity(&far), 2.5);
+        }
+
+        #[test]
+        fn empty_population() {
+            assert_eq!(population_diversity::<TestIndividual>(&[]), 0.0);
+        }
+    }
+
+    mod adaptive_gaussian_mutation {
+        use super::*;
+        use approx::assert_relative_eq;
+        use std::sync::{Arc, Mutex};
+
+        #[test]
+        fn diversity_drop_increases_coeff() {
+            let mut mutation = AdaptiveGaussianMutation::new(0.5, 0.1, 0.5, 1.0);
+
+            mutation.on_generation(2.0);
+            assert_relative_eq!(mutation.coeff(), 0.1);
+
+            mutation.on_generation(0.5);
+            assert_relative_eq!(mutation.coeff(), 0.3);
+
+            mutation.on_generation(0.0);
+            assert_relative_eq!(mutation.coeff(), 0.5);
+        }
+
+        #[test]
+        fn evolve_reports_diversity() {
+            struct RecordingMutation(Arc<Mutex<Vec<f32>>>);
+
+            impl MutationMethod for RecordingMutation {
+                fn mutate(&self, _: &mut dyn RngCore, _: &mut Chromosome) {
+                    //
+                }
+
+                fn wants_diversity(&self) -> bool {
+                    true
+                }
+
+                fn on_generation(&mut self, population_diversity: f32) {
+                    self.0.lock().unwrap().push(population_diversity);
+                }
+            }
+
+            let diversities = Arc::new(Mutex::new(Vec::new()));
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let mut ga = GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover,
+                RecordingMutation(diversities.clone()),
+            );
+
+            let converged: Vec<_> = (0..4)
+                .map(|_| TestIndividual::create(vec![1.0, 2.0].into_iter().collect()))
+                .collect();
+
+            let diverse: Vec<_> = [[0.0, 0.0], [2.0, 4.0]]
+                .iter()
+                .map(|genes| TestIndividual::create(genes.iter().copied().collect()))
+                .collect();
+
+            ga.evolve(&mut rng, &converged);
+            ga.evolve(&mut rng, &diverse);
+
+            // Per gene variances are 1.0 and 4.0
+            assert_eq!(*diversities.lock().unwrap(), [0.0, 2.5]);
+        }
+
+        #[test]
+        fn evolve_skips_diversity_unless_wanted() {
+            struct IndifferentMutation;
+
+            impl MutationMethod for IndifferentMutation {
+                fn mutate(&self, _: &mut dyn RngCore, _: &mut Chromosome) {
+                    //
+                }
+
+                fn on_generation(&mut self, _: f32) {
+                    panic!("diversity got computed even though it's not wanted");
+                }
+            }
+
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let mut ga = GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover,
+                IndifferentMutation,
+            );
+
+            let population: Vec<_> = (0..4)
+                .map(|_| TestIndividual::create(vec![1.0, 2.0].into_iter().collect()))
+                .collect();
+
+            ga.evolve(&mut rng, &population);
+        }
+    }
+
+    mod decaying_gaussian_mutation {
+        use super::*;
+        use approx::assert_relative_eq;
+        use std::sync::{Arc, Mutex};
+
+        #[test]
+        fn coeff_decays_with_generations() {
+            let mut mutation = DecayingGaussianMutation::new(0.5, 0.8, 0.9);
+
+            assert_relative_eq!(mutation.coeff(), 0.8);
+
+            for generation in 0..=10 {
+                mutation.set_generation(generation);
+            }
+
+            assert_relative_eq!(mutation.coeff(), 0.8 * 0.9f32.powi(10));
+        }
+
+        #[test]
+        fn genetic_algorithm_forwards_generation() {
+            struct RecordingMutation(Arc<Mutex<Vec<usize>>>);
+
+            impl MutationMethod for RecordingMutation {
+                fn mutate(&self, _: &mut dyn RngCore, _: &mut Chromosome) {
+                    //
+                }
+
+                fn set_generation(&mut self, generation: usize) {
+                    self.0.lock().unwrap().push(generation);
+                }
+            }
+
+            let generations = Arc::new(Mutex::new(Vec::new()));
+
+            let mut ga = GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover,
+                RecordingMutation(generations.clone()),
+            );
+
+            ga.set_generation(0);
+            ga.set_generation(1);
+
+            assert_eq!(*generations.lock().unwrap(), [0, 1]);
+        }
+    }
+
+    mod uniform_mutation {
+        use super::*;
+
+        fn actual(chance: f32) -> Vec<f32> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
+
+            UniformMutation::new(chance, -0.5, 0.5).mutate(&mut rng, &mut child);
+
+            child.into_iter().collect()
+        }
+
+        mod given_zero_chance {
+            use approx::assert_relative_eq;
+
+            #[test]
+            fn does_not_change_the_original_chromosome() {
+                let actual = super::actual(0.0);
+                let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+                assert_relative_eq!(actual.as_slice(), expected.as_slice());
+            }
+        }
+
+        mod given_max_chance {
+            #[test]
+            fn replaces_every_gene_within_bounds() {
+                let actual = super::actual(1.0);
+
+                for gene in actual {
+                    assert!((-0.5..=0.5).contains(&gene));
+                }
+            }
+        }
+    }
+
+    mod swap_mutation {
+        use super::*;
+
+        #[test]
+        fn reorders_genes_without_changing_them() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let original: Chromosome = (1..=10).map(|n| n as f32).collect();
+            let mut child = original.clone();
+
+            SwapMutation::new(0.5).mutate(&mut rng, &mut child);
+
+            let mut sorted: Vec<_> = child.iter().copied().collect();
+            sorted.sort_by(|a, b| a.total_cmp(b));
+
+            assert_eq!(sorted, original.genes);
+            assert_ne!(child.genes, original.genes);
+        }
+
+        #[test]
+        fn given_single_gene_does_nothing() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = vec![1.0].into_iter().collect();
+
+            SwapMutation::new(1.0).mutate(&mut rng, &mut child);
+
+            assert_eq!(child.genes, vec![1.0]);
+        }
+    }
+
+    mod scripted_mutation {
+        use super::*;
+
+        #[test]
+        fn applies_perturbations_in_order() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = vec![1.0, 2.0, 3.0, 4.0].into_iter().collect();
+
+            ScriptedMutation::new([0.5, -1.0, 0.25, 0.0]).mutate(&mut rng, &mut child);
+
+            assert_eq!(child.genes, vec![1.5, 1.0, 3.25, 4.0]);
+        }
+
+        #[test]
+        fn consecutive_children_get_consecutive_perturbations() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mutation = ScriptedMutation::new([0.5, -1.0, 0.25, 2.0]);
+
+            let mut first: Chromosome = vec![1.0, 2.0].into_iter().collect();
+            let mut second = first.clone();
+
+            mutation.mutate(&mut rng, &mut first);
+            mutation.mutate(&mut rng, &mut second);
+
+            assert_eq!(first.genes, vec![1.5, 1.0]);
+            assert_eq!(second.genes, vec![1.25, 4.0]);
+        }
+
+        #[test]
+        #[should_panic(expected = "script ran out of perturbations")]
+        fn panics_when_script_runs_out() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mutation = ScriptedMutation::new([0.5, -1.0, 0.25]);
+            let mut child: Chromosome = vec![1.0, 2.0].into_iter().collect();
+
+            mutation.mutate(&mut rng, &mut child.clone());
+            mutation.mutate(&mut rng, &mut child);
+        }
+
+        #[test]
+        fn evolve_produces_exact_children() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let mut ga = GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover,
+                ScriptedMutation::new((0..12).map(|n| n as f32)),
+            );
+
+            let population: Vec<_> = (0..4)
+                .map(|_| TestIndividual::create(vec![1.0, 2.0, 3.0].into_iter().collect()))
+                .collect();
+
+            let (population, _) = ga.evolve(&mut rng, &population);
+
+            let genes: Vec<_> = population
+                .iter()
+                .map(|individual| individual.chromosome().genes.clone())
+                .collect();
+
+            assert_eq!(
+                genes,
+                vec![
+                    vec![1.0, 3.0, 5.0],
+                    vec![4.0, 6.0, 8.0],
+                    vec![7.0, 9.0, 11.0],
+                    vec![10.0, 12.0, 14.0],
+                ]
+            );
+        }
+    }
+
+    mod masked_mutation {
+        use super::*;
+
+        #[test]
+        fn leaves_masked_genes_intact() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mask = vec![true, false, true, false, false];
+            let mutation = MaskedMutation::new(GaussianMutation::new(1.0, 0.5), mask.clone());
+
+            for _ in 0..100 {
+                let original: Chromosome = (0..5).map(|gene| gene as f32).collect();
+                let mut child = original.clone();
+
+                mutation.mutate(&mut rng, &mut child);
+
+                for ((gene, original), masked) in child.iter().zip(original.iter()).zip(&mask) {
+                    if *masked {
+                        assert_eq!(gene, original);
+                    } else {
+                        assert_ne!(gene, original);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn forwards_generation_to_inner_method() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = vec![0.0, 0.0].into_iter().collect();
+
+            let mut mutation = MaskedMutation::new(
+                DecayingGaussianMutation::new(1.0, 1.0, 0.0),
+                vec![false, false],
+            );
+
+            mutation.set_generation(1);
+            mutation.mutate(&mut rng, &mut child);
+
+            assert_eq!(child.genes, vec![0.0, 0.0]);
+        }
+
+        #[test]
+        fn forwards_wants_diversity_to_inner_method() {
+            let adaptive = AdaptiveGaussianMutation::new(0.5, 0.1, 1.0, 1.0);
+
+            assert!(MaskedMutation::new(adaptive, vec![false]).wants_diversity());
+            assert!(
+                !MaskedMutation::new(GaussianMutation::new(0.5, 0.1), vec![false])
+                    .wants_diversity()
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "mask doesn't match chromosome's length")]
+        fn panics_given_mask_of_different_length() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+
+            MaskedMutation::new(GaussianMutation::new(1.0, 0.5), vec![true])
+                .mutate(&mut rng, &mut child);
+        }
+    }
+
+    mod segmented_mutation {
+        use super::*;
+
+        fn mutation(biases: GaussianMutation, weights: GaussianMutation) -> SegmentedMutation {
+            SegmentedMutation::new(&topology(&[2, 3, 1]), biases, weights)
+        }
+
+        fn mutate(biases: GaussianMutation, weights: GaussianMutation) -> Vec<f32> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = vec![0.0; 13].into_iter().collect();
+
+            mutation(biases, weights).mutate(&mut rng, &mut child);
+
+            child.genes
+        }
+
+        #[test]
+        fn finds_biases() {
+            let mutation = mutation(
+                GaussianMutation::new(1.0, 1.0),
+                GaussianMutation::new(1.0, 1.0),
+            );
+
+            let (b, w) = (true, false);
+
+            assert_eq!(
+                mutation.is_bias,
+                vec![b, w, w, b, w, w, b, w, w, b, w, w, w]
+            );
+        }
+
+        #[test]
+        fn biases_get_bias_coefficient() {
+            let genes = mutate(
+                GaussianMutation::new(1.0, 0.1),
+                GaussianMutation::new(1.0, 0.0),
+            );
+
+            for (idx, gene) in genes.into_iter().enumerate() {
+                if [0, 3, 6, 9].contains(&idx) {
+                    assert!(gene != 0.0 && gene.abs() <= 0.1);
+                } else {
+                    assert_eq!(gene, 0.0);
+                }
+            }
+        }
+
+        #[test]
+        fn weights_get_weight_coefficient() {
+            let genes = mutate(
+                GaussianMutation::new(1.0, 0.0),
+                GaussianMutation::new(1.0, 5.0),
+            );
+
+            for (idx, gene) in genes.into_iter().enumerate() {
+                if [0, 3, 6, 9].contains(&idx) {
+                    assert_eq!(gene, 0.0);
+                } else {
+                    assert!(gene != 0.0 && gene.abs() <= 5.0);
+                }
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "layers don't match chromosome's length")]
+        fn panics_given_chromosome_of_different_length() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = vec![0.0; 12].into_iter().collect();
+
+            mutation(
+                GaussianMutation::new(1.0, 1.0),
+                GaussianMutation::new(1.0, 1.0),
+            )
+            .mutate(&mut rng, &mut child);
+        }
+    }
 }
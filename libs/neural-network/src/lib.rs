@@ -1,14 +1,191 @@
 use rand::{Rng, RngCore};
+use std::error::Error;
+use std::fmt;
 use std::iter::once;
+use std::ops::Range;
 
 #[derive(Debug)]
 pub struct Network {
     layers: Vec<Layer>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct LayerTopology {
     pub neurons: usize,
+
+    /// Activation applied to this layer's outputs; ignored for the input
+    /// layer, since it doesn't compute anything
+    pub activation: Activation,
+}
+
+impl LayerTopology {
+    pub fn new(neurons: usize) -> Self {
+        Self {
+            neurons,
+            activation: Activation::default(),
+        }
+    }
+
+    pub fn with_activation(mut self, activation: Activation) -> Self {
+        self.activation = activation;
+        self
+    }
+}
+
+/// Layers of a network, checked to make sense: there are at least two of
+/// them (input and output) and none of them is empty
+#[derive(Debug, PartialEq)]
+pub struct NetworkTopology {
+    layers: Vec<LayerTopology>,
+}
+
+impl NetworkTopology {
+    pub fn new(layers: Vec<LayerTopology>) -> Result<Self, TopologyError> {
+        if layers.len() < 2 {
+            return Err(TopologyError::TooFewLayers {
+                layers: layers.len(),
+            });
+        }
+
+        if let Some(layer) = layers.iter().position(|layer| layer.neurons == 0) {
+            return Err(TopologyError::EmptyLayer { layer });
+        }
+
+        Ok(Self { layers })
+    }
+
+    pub fn layers(&self) -> &[LayerTopology] {
+        &self.layers
+    }
+
+    /// Returns the number of neurons of each layer
+    pub fn neurons(&self) -> Vec<usize> {
+        self.layers.iter().map(|layer| layer.neurons).collect()
+    }
+
+    /// Returns the number of weights (including biases) a network with this
+    /// topology has; see [`weight_count()`]
+    pub fn weight_count(&self) -> usize {
+        weight_count(&self.neurons())
+    }
+
+    /// Returns where weights of each non-input layer lie among all the
+    /// network's weights, as laid out by [`Network::weights()`]
+    pub fn layer_ranges(&self) -> Vec<Range<usize>> {
+        self.layers
+            .windows(2)
+            .scan(0, |start, layers| {
+                let range = *start..*start + layer_len(layers[0].neurons, layers[1].neurons);
+
+                *start = range.end;
+                Some(range)
+            })
+            .collect()
+    }
+
+    /// Returns indices of biases among all the network's weights, as laid out
+    /// by [`Network::weights()`]
+    pub fn bias_indices(&self) -> Vec<usize> {
+        self.layer_ranges()
+            .into_iter()
+            .zip(&self.layers[1..])
+            .flat_map(|(range, layer)| {
+                // Each neuron's bias is followed by its weights
+                let neuron_len = range.len() / layer.neurons;
+
+                range.step_by(neuron_len)
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TopologyError {
+    TooFewLayers { layers: usize },
+    EmptyLayer { layer: usize },
+    WeightCountMismatch { expected: usize, actual: usize },
+}
+
+impl fmt::Display for TopologyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewLayers { layers } => {
+                write!(f, "got {layers} layer(s), but a network needs at least 2")
+            }
+            Self::EmptyLayer { layer } => write!(f, "layer #{layer} has no neurons"),
+            Self::WeightCountMismatch { expected, actual } => {
+                write!(f, "got {actual} weight(s), but topology needs {expected}")
+            }
+        }
+    }
+}
+
+impl Error for TopologyError {}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Activation {
+    #[default]
+    Relu,
+    LeakyRelu,
+    Sigmoid,
+    Tanh,
+}
+
+impl Activation {
+    const LEAKY_RELU_SLOPE: f32 = 0.01;
+
+    pub fn apply(self, x: f32) -> f32 {
+        match self {
+            Self::Relu => x.max(0.0),
+            Self::LeakyRelu => {
+                if x >= 0.0 {
+                    x
+                } else {
+                    Self::LEAKY_RELU_SLOPE * x
+                }
+            }
+            Self::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+            Self::Tanh => x.tanh(),
+        }
+    }
+}
+
+/// How random networks get their initial weights
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InitStrategy {
+    /// Weights & biases drawn uniformly from `[-1.0, 1.0]`
+    #[default]
+    Uniform,
+
+    /// Xavier / Glorot: weights with variance of `1 / fan_in`, zero biases;
+    /// suits `Sigmoid` and `Tanh`
+    Xavier,
+
+    /// He: weights with variance of `2 / fan_in`, zero biases; suits `Relu`
+    /// and `LeakyRelu`
+    He,
+}
+
+impl InitStrategy {
+    /// Returns a random weight of a neuron with given number of inputs
+    fn weight(self, rng: &mut dyn RngCore, fan_in: usize) -> f32 {
+        // Uniform distribution over [-a, a] has variance of a^2 / 3
+        let bound = match self {
+            Self::Uniform => 1.0,
+            Self::Xavier => (3.0 / fan_in as f32).sqrt(),
+            Self::He => (6.0 / fan_in as f32).sqrt(),
+        };
+
+        rng.gen_range(-bound..=bound)
+    }
+
+    fn bias(self, rng: &mut dyn RngCore) -> f32 {
+        match self {
+            Self::Uniform => rng.gen_range(-1.0..=1.0),
+            Self::Xavier | Self::He => 0.0,
+        }
+    }
 }
 
 impl Network {
@@ -18,17 +195,50 @@ impl Network {
             .fold(inputs, |inputs, layer| layer.propagate(inputs))
     }
 
-    pub fn random(rng: &mut dyn RngCore, layers: &[LayerTopology]) -> Self {
-        assert!(layers.len() > 1);
+    pub fn random(rng: &mut dyn RngCore, topology: &NetworkTopology) -> Self {
+        Self::random_with_init(rng, topology, InitStrategy::default())
+    }
 
-        let layers = layers
+    pub fn random_with_init(
+        rng: &mut dyn RngCore,
+        topology: &NetworkTopology,
+        init: InitStrategy,
+    ) -> Self {
+        let layers = topology
+            .layers
             .windows(2)
-            .map(|layers| Layer::random(rng, layers[0].neurons, layers[1].neurons))
+            .map(|layers| {
+                Layer::random(
+                    rng,
+                    layers[0].neurons,
+                    layers[1].neurons,
+                    layers[1].activation,
+                    init,
+                )
+            })
             .collect();
 
         Self { layers }
     }
 
+    /// Overwrites all weights & biases with random ones, in place
+    pub fn randomize(&mut self, rng: &mut dyn RngCore, init: InitStrategy) {
+        for neuron in self.layers.iter_mut().flat_map(|layer| &mut layer.neurons) {
+            neuron.randomize(rng, init);
+        }
+    }
+
+    pub fn topology(&self) -> Vec<LayerTopology> {
+        let inputs = self.layers[0].neurons[0].weights.len();
+
+        once(LayerTopology::new(inputs))
+            .chain(self.layers.iter().map(|layer| LayerTopology {
+                neurons: layer.neurons.len(),
+                activation: layer.activation,
+            }))
+            .collect()
+    }
+
     pub fn weights(&self) -> impl Iterator<Item = f32> + '_ {
         self.layers
             .iter()
@@ -37,55 +247,125 @@ impl Network {
             .copied()
     }
 
-    pub fn from_weights(layers: &[LayerTopology], weights: impl Iterator<Item = f32>) -> Self {
-        assert!(layers.len() > 1);
+    /// Returns weights & biases of each non-input layer, for inspection
+    pub fn layer_weights(&self) -> Vec<LayerWeights> {
+        self.layers
+            .iter()
+            .map(|layer| LayerWeights {
+                weights: layer
+                    .neurons
+                    .iter()
+                    .map(|neuron| neuron.weights.clone())
+                    .collect(),
+                biases: layer.neurons.iter().map(|neuron| neuron.bias).collect(),
+            })
+            .collect()
+    }
+
+    pub fn from_weights(
+        topology: &NetworkTopology,
+        weights: impl IntoIterator<Item = f32>,
+    ) -> Result<Self, TopologyError> {
+        let weights: Vec<_> = weights.into_iter().collect();
+        let expected = topology.weight_count();
+
+        if weights.len() != expected {
+            return Err(TopologyError::WeightCountMismatch {
+                expected,
+                actual: weights.len(),
+            });
+        }
 
         let mut weights = weights.into_iter();
 
-        let layers = layers
+        let layers = topology
+            .layers
             .windows(2)
-            .map(|layers| Layer::from_weights(layers[0].neurons, layers[1].neurons, &mut weights))
+            .map(|layers| {
+                Layer::from_weights(
+                    layers[0].neurons,
+                    layers[1].neurons,
+                    layers[1].activation,
+                    &mut weights,
+                )
+            })
             .collect();
 
-        if weights.next().is_some() {
-            panic!("got too many weights")
-        }
-
-        Self { layers }
+        Ok(Self { layers })
     }
 }
 
+/// Returns the number of weights (including biases) of a network with given
+/// layer sizes, i.e. what [`Network::from_weights()`] expects to get
+pub fn weight_count(layers: &[usize]) -> usize {
+    layers
+        .windows(2)
+        .map(|layers| layer_len(layers[0], layers[1]))
+        .sum()
+}
+
+/// Returns the number of weights (including biases) of a layer with given
+/// number of inputs and neurons
+fn layer_len(inputs: usize, neurons: usize) -> usize {
+    (inputs + 1) * neurons
+}
+
+/// Read-only view of a layer's parameters, as returned by
+/// [`Network::layer_weights()`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerWeights {
+    /// `weights[n][i]` is the weight between `i`-th input and `n`-th neuron
+    pub weights: Vec<Vec<f32>>,
+
+    /// `biases[n]` is the bias of `n`-th neuron
+    pub biases: Vec<f32>,
+}
+
 #[derive(Debug)]
 struct Layer {
     neurons: Vec<Neuron>,
+    activation: Activation,
 }
 
 impl Layer {
     fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
         self.neurons
             .iter()
-            .map(|neuron| neuron.propagate(&inputs))
+            .map(|neuron| neuron.propagate(&inputs, self.activation))
             .collect()
     }
 
-    fn random(rng: &mut dyn RngCore, input_size: usize, output_size: usize) -> Self {
+    fn random(
+        rng: &mut dyn RngCore,
+        input_size: usize,
+        output_size: usize,
+        activation: Activation,
+        init: InitStrategy,
+    ) -> Self {
         let neurons = (0..output_size)
-            .map(|_| Neuron::random(rng, input_size))
+            .map(|_| Neuron::random(rng, input_size, init))
             .collect();
 
-        Self { neurons }
+        Self {
+            neurons,
+            activation,
+        }
     }
 
     fn from_weights(
         input_size: usize,
         output_size: usize,
+        activation: Activation,
         weights: &mut dyn Iterator<Item = f32>,
     ) -> Self {
         let neurons = (0..output_size)
             .map(|_| Neuron::from_weights(input_size, weights))
             .collect();
 
-        Self { neurons }
+        Self {
+            neurons,
+            activation,
+        }
     }
 }
 
@@ -96,7 +376,7 @@ struct Neuron {
 }
 
 impl Neuron {
-    fn propagate(&self, inputs: &[f32]) -> f32 {
+    fn propagate(&self, inputs: &[f32], activation: Activation) -> f32 {
         assert_eq!(inputs.len(), self.weights.len());
 
         let output = inputs
@@ -105,22 +385,32 @@ impl Neuron {
             .map(|(input, weight)| input * weight)
             .sum::<f32>();
 
-        (self.bias + output).max(0.0)
+        activation.apply(self.bias + output)
     }
 
-    fn random(rng: &mut dyn RngCore, input_size: usize) -> Self {
-        let bias = rng.gen_range(-1.0..=1.0);
+    fn random(rng: &mut dyn RngCore, input_size: usize, init: InitStrategy) -> Self {
+        let bias = init.bias(rng);
 
-        let weights = (0..input_size).map(|_| rng.gen_range(-1.0..=1.0)).collect();
+        let weights = (0..input_size)
+            .map(|_| init.weight(rng, input_size))
+            .collect();
 
         Self { bias, weights }
     }
 
+    fn randomize(&mut self, rng: &mut dyn RngCore, init: InitStrategy) {
+        let input_size = self.weights.len();
+
+        self.bias = init.bias(rng);
+
+        for weight in &mut self.weights {
+            *weight = init.weight(rng, input_size);
+        }
+    }
+
     fn from_weights(input_size: usize, weights: &mut dyn Iterator<Item = f32>) -> Self {
-        let bias = weights.next().expect("got not enough weights");
-        let weights = (0..input_size)
-            .map(|_| weights.next().expect("got not enough weights"))
-            .collect();
+        let bias = weights.next().unwrap();
+        let weights = (0..input_size).map(|_| weights.next().unwrap()).collect();
 
         Self { bias, weights }
     }
@@ -136,7 +426,7 @@ mod tests {
     #[test]
     fn random() {
         let mut rng = ChaCha8Rng::from_seed(Default::default());
-        let neuron = Neuron::random(&mut rng, 4);
+        let neuron = Neuron::random(&mut rng, 4, InitStrategy::Uniform);
 
         assert_relative_eq!(neuron.bias, -0.6255188);
         assert_relative_eq!(
@@ -145,6 +435,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn randomize() {
+        let topology =
+            NetworkTopology::new(vec![LayerTopology::new(4), LayerTopology::new(3)]).unwrap();
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let expected = Network::random(&mut rng, &topology);
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut actual = Network::from_weights(&topology, [0.0; 15]).unwrap();
+
+        actual.randomize(&mut rng, InitStrategy::Uniform);
+
+        assert_eq!(
+            actual.weights().collect::<Vec<_>>(),
+            expected.weights().collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn propagate() {
         let neuron = Neuron {
@@ -152,10 +461,10 @@ mod tests {
             weights: vec![-0.3, 0.8],
         };
 
-        assert_relative_eq!(neuron.propagate(&[-10.0, -10.0]), 0.0);
+        assert_relative_eq!(neuron.propagate(&[-10.0, -10.0], Activation::Relu), 0.0);
 
         assert_relative_eq!(
-            neuron.propagate(&[0.5, 1.0]),
+            neuron.propagate(&[0.5, 1.0], Activation::Relu),
             (-0.3 * 0.5) + (0.8 * 1.0) + 0.5
         )
     }
@@ -163,20 +472,22 @@ mod tests {
     #[test]
     fn weights() {
         let network = Network {
-            layers: vec![Layer {
-                neurons: vec![
-                    Neuron {
+            layers: vec![
+                Layer {
+                    neurons: vec![Neuron {
                         bias: 0.1,
                         weights: vec![0.2, 0.3, 0.4],
-                    },
-                    Layer {
-                        neurons: vec![Neuron {
-                            bias: 0.5,
-                            weights: vec![0.6, 0.7, 0.8],
-                        }],
-                    },
-                ],
-            }],
+                    }],
+                    activation: Activation::Relu,
+                },
+                Layer {
+                    neurons: vec![Neuron {
+                        bias: 0.5,
+                        weights: vec![0.6, 0.7, 0.8],
+                    }],
+                    activation: Activation::Relu,
+                },
+            ],
         };
 
         let actual: Vec<_> = network.weights().collect();
@@ -185,15 +496,260 @@ mod tests {
         assert_relative_eq!(actual.as_slice(), expected.as_slice());
     }
 
+    #[test]
+    fn layer_weights() {
+        let topology = NetworkTopology::new(vec![
+            LayerTopology::new(2),
+            LayerTopology::new(2),
+            LayerTopology::new(1),
+        ])
+        .unwrap();
+        let weights = (1..=9).map(|n| n as f32 / 10.0);
+        let network = Network::from_weights(&topology, weights).unwrap();
+
+        assert_eq!(
+            network.layer_weights(),
+            vec![
+                LayerWeights {
+                    weights: vec![vec![0.2, 0.3], vec![0.5, 0.6]],
+                    biases: vec![0.1, 0.4],
+                },
+                LayerWeights {
+                    weights: vec![vec![0.8, 0.9]],
+                    biases: vec![0.7],
+                },
+            ]
+        );
+    }
+
     #[test]
     fn from_weights() {
-        let layers = &[LayerTopology { neurons: 3 }, LayerTopology { neurons: 2 }];
+        let topology =
+            NetworkTopology::new(vec![LayerTopology::new(3), LayerTopology::new(2)]).unwrap();
 
         let weights = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8];
 
-        let network = Network::from_weights(layers, weights.clone());
+        let network = Network::from_weights(&topology, weights.clone()).unwrap();
         let actual: Vec<_> = network.weights().collect();
 
         assert_relative_eq!(actual.as_slice(), weights.as_slice());
     }
+
+    #[test]
+    fn from_weights_rejects_mismatched_weights() {
+        let topology =
+            NetworkTopology::new(vec![LayerTopology::new(3), LayerTopology::new(2)]).unwrap();
+
+        for actual in [7, 9] {
+            let err = Network::from_weights(&topology, vec![0.5; actual]).unwrap_err();
+
+            assert_eq!(
+                err,
+                TopologyError::WeightCountMismatch {
+                    expected: 8,
+                    actual
+                }
+            );
+        }
+    }
+
+    mod network_topology {
+        use super::*;
+
+        #[test]
+        fn valid() {
+            let topology = NetworkTopology::new(vec![
+                LayerTopology::new(2),
+                LayerTopology::new(3),
+                LayerTopology::new(1),
+            ])
+            .unwrap();
+
+            assert_eq!(topology.layers().len(), 3);
+            assert_eq!(topology.neurons(), vec![2, 3, 1]);
+            assert_eq!(topology.weight_count(), 13);
+            assert_eq!(topology.layer_ranges(), vec![0..9, 9..13]);
+            assert_eq!(topology.bias_indices(), vec![0, 3, 6, 9]);
+        }
+
+        #[test]
+        fn rejects_too_few_layers() {
+            assert_eq!(
+                NetworkTopology::new(vec![LayerTopology::new(2)]),
+                Err(TopologyError::TooFewLayers { layers: 1 })
+            );
+        }
+
+        #[test]
+        fn rejects_empty_layers() {
+            let err = NetworkTopology::new(vec![
+                LayerTopology::new(2),
+                LayerTopology::new(0),
+                LayerTopology::new(1),
+            ])
+            .unwrap_err();
+
+            assert_eq!(err, TopologyError::EmptyLayer { layer: 1 });
+            assert_eq!(err.to_string(), "layer #1 has no neurons");
+        }
+    }
+
+    #[test]
+    fn weight_count() {
+        assert_eq!(super::weight_count(&[]), 0);
+        assert_eq!(super::weight_count(&[3]), 0);
+        assert_eq!(super::weight_count(&[3, 2]), 8);
+        assert_eq!(super::weight_count(&[2, 3, 1]), 13);
+        assert_eq!(super::weight_count(&[18, 4, 3, 2]), 99);
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let topology = NetworkTopology::new(vec![
+            LayerTopology::new(5),
+            LayerTopology::new(4),
+            LayerTopology::new(2),
+        ])
+        .unwrap();
+
+        let network = Network::random(&mut rng, &topology);
+
+        assert_eq!(network.weights().count(), super::weight_count(&[5, 4, 2]));
+    }
+
+    #[test]
+    fn topology() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let topology = NetworkTopology::new(vec![
+            LayerTopology::new(3),
+            LayerTopology::new(4).with_activation(Activation::Tanh),
+            LayerTopology::new(2),
+        ])
+        .unwrap();
+
+        let topology = Network::random(&mut rng, &topology).topology();
+
+        let actual: Vec<_> = topology
+            .iter()
+            .map(|layer| (layer.neurons, layer.activation))
+            .collect();
+
+        let expected = vec![
+            (3, Activation::Relu),
+            (4, Activation::Tanh),
+            (2, Activation::Relu),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    mod init_strategy {
+        use super::*;
+        use approx::assert_relative_eq;
+
+        fn variance(init: InitStrategy, fan_in: usize) -> f32 {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let topology =
+                NetworkTopology::new(vec![LayerTopology::new(fan_in), LayerTopology::new(500)])
+                    .unwrap();
+
+            let network = Network::random_with_init(&mut rng, &topology, init);
+
+            let weights: Vec<_> = network.layers[0]
+                .neurons
+                .iter()
+                .flat_map(|neuron| &neuron.weights)
+                .collect();
+
+            weights.iter().map(|w| w.powi(2)).sum::<f32>() / weights.len() as f32
+        }
+
+        #[test]
+        fn xavier_variance_scales_with_fan_in() {
+            assert_relative_eq!(
+                variance(InitStrategy::Xavier, 10),
+                1.0 / 10.0,
+                epsilon = 0.005
+            );
+            assert_relative_eq!(
+                variance(InitStrategy::Xavier, 100),
+                1.0 / 100.0,
+                epsilon = 0.0005
+            );
+        }
+
+        #[test]
+        fn he_variance_scales_with_fan_in() {
+            assert_relative_eq!(variance(InitStrategy::He, 10), 2.0 / 10.0, epsilon = 0.01);
+        }
+
+        #[test]
+        fn weight_count_does_not_depend_on_strategy() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let topology = NetworkTopology::new(vec![
+                LayerTopology::new(3),
+                LayerTopology::new(4),
+                LayerTopology::new(2),
+            ])
+            .unwrap();
+
+            for init in [
+                InitStrategy::Uniform,
+                InitStrategy::Xavier,
+                InitStrategy::He,
+            ] {
+                let network = Network::random_with_init(&mut rng, &topology, init);
+
+                assert_eq!(network.weights().count(), 26);
+            }
+        }
+    }
+
+    mod activation {
+        use super::*;
+        use approx::assert_relative_eq;
+
+        #[test]
+        fn relu() {
+            assert_relative_eq!(Activation::Relu.apply(-2.0), 0.0);
+            assert_relative_eq!(Activation::Relu.apply(0.0), 0.0);
+            assert_relative_eq!(Activation::Relu.apply(2.0), 2.0);
+        }
+
+        #[test]
+        fn leaky_relu() {
+            assert_relative_eq!(Activation::LeakyRelu.apply(-2.0), -0.02);
+            assert_relative_eq!(Activation::LeakyRelu.apply(0.0), 0.0);
+            assert_relative_eq!(Activation::LeakyRelu.apply(2.0), 2.0);
+        }
+
+        #[test]
+        fn sigmoid() {
+            assert_relative_eq!(Activation::Sigmoid.apply(-2.0), 0.11920292);
+            assert_relative_eq!(Activation::Sigmoid.apply(0.0), 0.5);
+            assert_relative_eq!(Activation::Sigmoid.apply(2.0), 0.8807971);
+        }
+
+        #[test]
+        fn tanh() {
+            assert_relative_eq!(Activation::Tanh.apply(-2.0), -0.9640276);
+            assert_relative_eq!(Activation::Tanh.apply(0.0), 0.0);
+            assert_relative_eq!(Activation::Tanh.apply(2.0), 0.9640276);
+        }
+
+        #[test]
+        fn network_applies_activation_per_layer() {
+            let topology = NetworkTopology::new(vec![
+                LayerTopology::new(1),
+                LayerTopology::new(1).with_activation(Activation::Tanh),
+                LayerTopology::new(1),
+            ])
+            .unwrap();
+
+            // bias 0.0, weight -2.0 in both layers
+            let network = Network::from_weights(&topology, [0.0, -2.0, 0.0, -2.0]).unwrap();
+
+            assert_relative_eq!(network.propagate(vec![1.0])[0], 2.0 * 0.9640276);
+        }
+    }
 }
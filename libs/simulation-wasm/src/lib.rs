@@ -13,7 +13,7 @@ impl Simulation {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
         let mut rng = thread_rng();
-        let sim = sim::Simulation::random(&mut rng);
+        let sim = sim::Simulation::random(sim::Config::default(), &mut rng);
 
         Self { rng, sim }
     }
@@ -22,6 +22,16 @@ impl Simulation {
         World::from(self.sim.world())
     }
 
+    /// Animals packed as `[x, y, rotation, speed]` each
+    pub fn animals_flat(&self) -> Vec<f32> {
+        self.sim.world().animals_flat()
+    }
+
+    /// Foods packed as `[x, y]` each
+    pub fn foods_flat(&self) -> Vec<f32> {
+        self.sim.world().foods_flat()
+    }
+
     pub fn step(&mut self) {
         self.sim.step(&mut self.rng);
     }
@@ -33,10 +43,36 @@ impl Simulation {
     pub fn train(&mut self) -> String {
         let stats = self.sim.train(&mut self.rng);
 
-        format!(
-            "min={:.2}, max={:.2}, avg={:.2}",
-            stats.min_fitness, stats.max_fitness, stats.avg_fitness
-        )
+        summarize(&stats)
+    }
+
+    /// Runs given number of whole generations without any rendering in
+    /// between, returning summary of each one (as returned by `train()`)
+    pub fn fast_forward(&mut self, generations: usize) -> Vec<String> {
+        self.sim
+            .train_generations(generations, &mut self.rng)
+            .iter()
+            .map(summarize)
+            .collect()
+    }
+}
+
+fn summarize(stats: &sim::Statistics) -> String {
+    format!(
+        "min={:.2}, max={:.2}, avg={:.2}, median={:.2}, satiation={}..{} (mean={:.2})",
+        stats.ga().min_fitness(),
+        stats.ga().max_fitness(),
+        stats.ga().avg_fitness(),
+        stats.ga().median_fitness(),
+        stats.min_satiation(),
+        stats.max_satiation(),
+        stats.mean_satiation()
+    )
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
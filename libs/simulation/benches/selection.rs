@@ -0,0 +1,89 @@
+//! Compares how quickly animals learn under different genetic algorithms.
+//!
+//! Besides criterion's timings, prints the average satiation reached by each
+//! configuration after `GENERATIONS` generations; all runs start from the
+//! same seeds, so they are comparable between each other.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use lib_genetic_algorithm as ga;
+use lib_simulation as sim;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+const GENERATIONS: usize = 3;
+const WORLD_SEED: u64 = 0;
+const TRAIN_SEED: u64 = 1;
+
+fn config() -> sim::Config {
+    sim::Config {
+        n_animals: 20,
+        n_foods: 20,
+        ..Default::default()
+    }
+}
+
+fn run<S>(ga: ga::GeneticAlgorithm<S>) -> f32
+where
+    S: ga::SelectionMethod,
+{
+    let config = config();
+    let world = sim::World::random(&config, &mut ChaCha8Rng::seed_from_u64(WORLD_SEED));
+    let mut sim = sim::Simulation::with_ga(world, ga, config);
+    let mut rng = ChaCha8Rng::seed_from_u64(TRAIN_SEED);
+
+    let stats = sim.train_generations(GENERATIONS, &mut rng);
+    let total: f32 = stats.iter().map(sim::Statistics::mean_satiation).sum();
+
+    total / stats.len() as f32
+}
+
+fn bench_config<S>(c: &mut Criterion, name: &str, ga: impl Fn() -> ga::GeneticAlgorithm<S>)
+where
+    S: ga::SelectionMethod,
+{
+    println!(
+        "{name}: average satiation after {GENERATIONS} generations = {:.2}",
+        run(ga())
+    );
+
+    c.bench_function(name, |b| b.iter(|| run(ga())));
+}
+
+fn selection_methods(c: &mut Criterion) {
+    bench_config(c, "roulette_wheel", || {
+        ga::GeneticAlgorithmBuilder::new().build()
+    });
+
+    bench_config(c, "tournament", || {
+        ga::GeneticAlgorithmBuilder::new()
+            .selection(ga::TournamentSelection::new(3))
+            .build()
+    });
+
+    bench_config(c, "rank", || {
+        ga::GeneticAlgorithmBuilder::new()
+            .selection(ga::RankSelection::new(1.5))
+            .build()
+    });
+
+    bench_config(c, "truncation_with_elites", || {
+        ga::GeneticAlgorithmBuilder::new()
+            .selection(ga::TruncationSelection::new(0.5))
+            .elite_count(2)
+            .build()
+    });
+
+    bench_config(c, "tournament_two_point", || {
+        ga::GeneticAlgorithmBuilder::new()
+            .selection(ga::TournamentSelection::new(3))
+            .crossover(ga::TwoPointCrossover)
+            .build()
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = selection_methods
+}
+criterion_main!(benches);
@@ -1,13 +1,35 @@
 use crate::*;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Animal {
     pub(crate) position: na::Point2<f32>,
     pub(crate) rotation: na::Rotation2<f32>,
     pub(crate) speed: f32,
+    /// Only used by [`MotorModel::Inertial`]
+    pub(crate) angular_velocity: f32,
     pub(crate) eye: Eye,
     pub(crate) satiation: usize,
+
+    /// Sum of rewards of the foods eaten, see [`Config::reward_fn`]
+    pub(crate) reward: f32,
+
+    pub(crate) energy: f32,
+    pub(crate) distance_traveled: f32,
+
+    /// Age of the generation at which this animal has first eaten
+    pub(crate) first_feed_age: Option<usize>,
+
+    /// Number of steps during which the animal has been idle, see
+    /// [`Config::idle_speed`]
+    pub(crate) idle_steps: usize,
+
+    pub(crate) radius: f32,
     pub(crate) brain: Brain,
+
+    /// What the eye has seen during the last step
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) last_vision: Vec<f32>,
 }
 
 impl Animal {
@@ -16,28 +38,81 @@ impl Animal {
             position: rng.gen(),
             rotation: rng.gen(),
             speed: 0.002,
+            angular_velocity: 0.0,
             eye,
             brain,
             satiation: 0,
+            reward: 0.0,
+            energy: ENERGY_MAX,
+            distance_traveled: 0.0,
+            first_feed_age: None,
+            idle_steps: 0,
+            radius: EAT_DISTANCE,
+            last_vision: Vec::new(),
         }
     }
 
-    pub fn random(rng: &mut dyn RngCore) -> Self {
-        let eye = Eye::default();
-        let brain = Brain::random(rng, &eye);
+    pub fn random(config: &Config, rng: &mut dyn RngCore) -> Self {
+        let eye = Eye::from(config);
+        let brain = Brain::random(
+            rng,
+            &eye,
+            &config.brain_hidden_layers,
+            config.brain_activations,
+            config.brain_init,
+        );
+
+        Self::new(eye, brain, rng).with_radius(config.food_collision_radius)
+    }
 
-        Self::new(eye, brain, rng)
+    /// Turns this animal into a fresh, random one - as if it was created
+    /// through [`Self::random()`], but reusing its allocations
+    pub(crate) fn reset(&mut self, config: &Config, rng: &mut dyn RngCore) {
+        self.brain.randomize(rng, config.brain_init);
+        self.position = rng.gen();
+        self.rotation = rng.gen();
+        self.speed = 0.002;
+        self.angular_velocity = 0.0;
+        self.restart();
+        self.radius = config.food_collision_radius;
+        self.last_vision.clear();
+    }
+
+    /// Forgets what this animal has achieved during the current generation,
+    /// keeping its brain and whereabouts
+    pub(crate) fn restart(&mut self) {
+        self.satiation = 0;
+        self.reward = 0.0;
+        self.energy = ENERGY_MAX;
+        self.distance_traveled = 0.0;
+        self.first_feed_age = None;
+        self.idle_steps = 0;
+    }
+
+    /// Overrides how close this animal has to get to a food to eat it
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
     }
 
     pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
         self.brain.as_chromosome()
     }
 
-    pub(crate) fn from_chromosome(chromosome: ga::Chromosome, rng: &mut dyn RngCore) -> Self {
-        let eye = Eye::default();
-        let brain = Brain::from_chromosome(chromosome, &eye);
+    pub(crate) fn from_chromosome(
+        config: &Config,
+        chromosome: ga::Chromosome,
+        rng: &mut dyn RngCore,
+    ) -> Self {
+        let eye = Eye::from(config);
+        let brain = Brain::from_chromosome(
+            chromosome,
+            &eye,
+            &config.brain_hidden_layers,
+            config.brain_activations,
+        );
 
-        Self::new(eye, brain, rng)
+        Self::new(eye, brain, rng).with_radius(config.food_collision_radius)
     }
 
     pub fn position(&self) -> na::Point2<f32> {
@@ -47,4 +122,146 @@ impl Animal {
     pub fn rotation(&self) -> na::Rotation2<f32> {
         self.rotation
     }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn satiation(&self) -> usize {
+        self.satiation
+    }
+
+    /// What the foods eaten so far are worth; with the default
+    /// [`Config::reward_fn`] it's the same as satiation
+    pub fn reward(&self) -> f32 {
+        self.reward
+    }
+
+    pub fn energy(&self) -> f32 {
+        self.energy
+    }
+
+    pub fn distance_traveled(&self) -> f32 {
+        self.distance_traveled
+    }
+
+    pub fn first_feed_age(&self) -> Option<usize> {
+        self.first_feed_age
+    }
+
+    pub fn idle_steps(&self) -> usize {
+        self.idle_steps
+    }
+
+    /// Remembers when the animal has eaten, unless it had already eaten
+    /// before
+    pub(crate) fn record_feed(&mut self, age: usize) {
+        self.first_feed_age.get_or_insert(age);
+    }
+
+    /// Rewards the animal for eating a food
+    pub(crate) fn feed(&mut self, reward_fn: &RewardFn) {
+        self.reward += reward_fn.reward(self.satiation as u32);
+        self.satiation += 1;
+    }
+
+    /// Takes away the last food the animal has eaten (along with its reward),
+    /// if any
+    pub(crate) fn unfeed(&mut self, reward_fn: &RewardFn) {
+        if self.satiation > 0 {
+            self.satiation -= 1;
+            self.reward -= reward_fn.reward(self.satiation as u32);
+        }
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Returns what the eye has seen during the last step (empty before the
+    /// first one), as laid out by [`Eye::process_vision()`]
+    pub fn last_vision(&self) -> &[f32] {
+        &self.last_vision
+    }
+
+    /// Returns whether this animal could've been born in a simulation with
+    /// given config, i.e. whether its eye and brain match the config's ones
+    pub(crate) fn is_compatible_with(&self, config: &Config) -> bool {
+        let eye = Eye::from(config);
+
+        self.eye.inputs() == eye.inputs()
+            && self.brain.is_compatible_with(
+                &eye,
+                &config.brain_hidden_layers,
+                config.brain_activations,
+            )
+    }
+
+    /// Runs animal's brain against given world without changing anything,
+    /// returning what it has seen and decided; the animal is assumed to be a
+    /// prey, i.e. to be looking at world's foods
+    pub fn debug_step(&self, world: &World, config: &Config) -> BrainTrace {
+        let others: Vec<_> = world
+            .animals
+            .iter()
+            .filter(|animal| !std::ptr::eq(*animal, self))
+            .map(|animal| animal.position)
+            .collect();
+
+        let predators: Vec<_> = world
+            .predators
+            .iter()
+            .map(|predator| predator.position)
+            .collect();
+
+        let vision = self.eye.process_vision(
+            self.position,
+            self.rotation,
+            &world.foods,
+            &others,
+            &predators,
+            &world.obstacles,
+        );
+
+        let outputs = self.brain.nn.propagate(vision.clone());
+        let mut response = outputs.clone();
+
+        sanitize(&mut response);
+
+        let motion = config
+            .motor_model
+            .motion(self, &MotorOutputs::new(response));
+
+        BrainTrace {
+            vision,
+            outputs,
+            speed_delta: motion.speed - self.speed,
+            rotation_delta: motion.rotation_delta,
+        }
+    }
+
+    pub fn is_starved(&self) -> bool {
+        self.energy <= 0.0
+    }
 }
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PopulationError {
+    Empty,
+    IncompatibleAnimal { index: usize },
+}
+
+impl fmt::Display for PopulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "population is empty"),
+            Self::IncompatibleAnimal { index } => write!(
+                f,
+                "animal #{} has eye or brain not matching the config",
+                index
+            ),
+        }
+    }
+}
+
+impl Error for PopulationError {}
@@ -1,20 +1,122 @@
 use crate::*;
 
+/// Shape of the satiation's contribution to fitness; concave ones flatten
+/// the advantage of animals that've stumbled upon a dense cluster of food
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SatiationTransform {
+    #[default]
+    Linear,
+    Sqrt,
+
+    /// `ln(1 + satiation)`
+    Log,
+}
+
+impl SatiationTransform {
+    pub fn apply(self, satiation: f32) -> f32 {
+        match self {
+            Self::Linear => satiation,
+            Self::Sqrt => satiation.sqrt(),
+            Self::Log => satiation.ln_1p(),
+        }
+    }
+}
+
+/// How much each of animal's achievements contributes to its fitness
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FitnessWeights {
+    /// Per reward of the foods eaten (see [`Config::reward_fn`]), after
+    /// applying `satiation_transform`
+    pub food: f32,
+
+    /// Per food eaten over distance traveled
+    pub efficiency: f32,
+
+    /// Per energy left at the end of generation, relative to the maximum
+    pub energy: f32,
+
+    /// For having eaten early: an animal that's eaten in the very first step
+    /// gets the whole weight, with the bonus decreasing linearly down to zero
+    /// at the end of the generation (of a default-length one, if generations
+    /// don't have a fixed length) and staying zero for ones that haven't
+    /// eaten at all
+    pub first_feed: f32,
+
+    /// Penalty for standing still (see [`Config::idle_speed`]): an animal
+    /// that's been idle for the whole generation (or for a default-length
+    /// one, if generations don't have a fixed length) loses the whole weight
+    pub idle: f32,
+
+    pub satiation_transform: SatiationTransform,
+}
+
+impl FitnessWeights {
+    fn is_zero(&self) -> bool {
+        self.food + self.efficiency + self.energy + self.first_feed + self.idle == 0.0
+    }
+}
+
+impl Default for FitnessWeights {
+    fn default() -> Self {
+        // Surviving energy acts as a tie-breaker between animals that have
+        // eaten the same amount of food
+        Self {
+            food: 1.0,
+            efficiency: 0.0,
+            energy: 1.0,
+            first_feed: 0.0,
+            idle: 0.0,
+            satiation_transform: SatiationTransform::default(),
+        }
+    }
+}
+
 pub struct AnimalIndividual {
     fitness: f32,
     chromosome: ga::Chromosome,
 }
 
 impl AnimalIndividual {
-    pub fn from_animal(animal: &Animal) -> Self {
+    /// Creates an individual with fitness being a weighted sum of animal's
+    /// achievements (see [`Config::fitness_weights`]); weights summing up to
+    /// zero fall back to plain reward
+    pub fn from_animal(animal: &Animal, config: &Config) -> Self {
+        let weights = &config.fitness_weights;
+        let food = animal.reward;
+
+        let fitness = if weights.is_zero() {
+            food
+        } else {
+            let efficiency = if animal.distance_traveled > 0.0 {
+                food / animal.distance_traveled
+            } else {
+                0.0
+            };
+
+            let generation_length =
+                config.generation_end.steps().unwrap_or(GENERATION_LENGTH) as f32;
+
+            let first_feed = animal
+                .first_feed_age
+                .map_or(0.0, |age| (1.0 - age as f32 / generation_length).max(0.0));
+
+            let idle = animal.idle_steps as f32 / generation_length;
+
+            weights.food * weights.satiation_transform.apply(food)
+                + weights.efficiency * efficiency
+                + weights.energy * animal.energy / ENERGY_MAX
+                + weights.first_feed * first_feed
+                - weights.idle * idle
+        };
+
         Self {
-            fitness: animal.satiation as f32,
+            fitness,
             chromosome: animal.as_chromosome(),
         }
     }
 
-    pub fn into_animal(self, rng: &mut dyn RngCore) -> Animal {
-        Animal::from_chromosome(self.chromosome, rng)
+    pub fn into_animal(self, config: &Config, rng: &mut dyn RngCore) -> Animal {
+        Animal::from_chromosome(config, self.chromosome, rng)
     }
 }
 
@@ -32,3 +134,192 @@ impl ga::Individual for AnimalIndividual {
         self.fitness
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use ga::Individual;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn animals() -> [Animal; 2] {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let config = Config::default();
+
+        // Wanders a lot, eats a lot, ends up exhausted
+        let mut glutton = Animal::random(&config, &mut rng);
+        glutton.satiation = 5;
+        glutton.reward = 5.0;
+        glutton.energy = 0.0;
+        glutton.distance_traveled = 10.0;
+
+        // Barely moves, eats a bit, keeps its energy
+        let mut sloth = Animal::random(&config, &mut rng);
+        sloth.satiation = 2;
+        sloth.reward = 2.0;
+        sloth.energy = ENERGY_MAX;
+        sloth.distance_traveled = 0.5;
+
+        [glutton, sloth]
+    }
+
+    fn config(fitness_weights: FitnessWeights) -> Config {
+        Config {
+            fitness_weights,
+            ..Default::default()
+        }
+    }
+
+    fn best(weights: FitnessWeights) -> usize {
+        let config = config(weights);
+
+        let fitnesses: Vec<_> = animals()
+            .iter()
+            .map(|animal| AnimalIndividual::from_animal(animal, &config).fitness())
+            .collect();
+
+        if fitnesses[0] > fitnesses[1] {
+            0
+        } else {
+            1
+        }
+    }
+
+    #[test]
+    fn weights_decide_which_animal_is_best() {
+        let weights = |food, efficiency, energy| FitnessWeights {
+            food,
+            efficiency,
+            energy,
+            ..Default::default()
+        };
+
+        assert_eq!(best(weights(1.0, 0.0, 0.0)), 0);
+        assert_eq!(best(weights(0.0, 1.0, 0.0)), 1);
+        assert_eq!(best(weights(0.0, 0.0, 1.0)), 1);
+        assert_eq!(best(weights(1.0, 0.0, 1.0)), 0);
+        assert_eq!(best(weights(1.0, 1.0, 1.0)), 1);
+    }
+
+    #[test]
+    fn zero_weights_fall_back_to_satiation() {
+        let config = config(FitnessWeights {
+            food: 0.0,
+            efficiency: 0.0,
+            energy: 0.0,
+            ..Default::default()
+        });
+
+        let [glutton, sloth] = animals();
+
+        assert_eq!(
+            AnimalIndividual::from_animal(&glutton, &config).fitness(),
+            5.0
+        );
+        assert_eq!(
+            AnimalIndividual::from_animal(&sloth, &config).fitness(),
+            2.0
+        );
+    }
+
+    #[test]
+    fn early_feeding_outranks_late_one() {
+        let [mut early, mut late] = animals();
+
+        for (animal, age) in [(&mut early, 10), (&mut late, 2000)] {
+            animal.satiation = 3;
+            animal.reward = 3.0;
+            animal.energy = ENERGY_MAX;
+            animal.distance_traveled = 1.0;
+            animal.first_feed_age = Some(age);
+        }
+
+        let fitness = |animal, config| AnimalIndividual::from_animal(animal, config).fitness();
+
+        let config = Config::default();
+
+        assert_eq!(fitness(&early, &config), fitness(&late, &config));
+
+        let config = self::config(FitnessWeights {
+            first_feed: 1.0,
+            ..Default::default()
+        });
+
+        assert!(fitness(&early, &config) > fitness(&late, &config));
+    }
+
+    #[test]
+    fn first_feed_follows_generation_length() {
+        let [mut animal, _] = animals();
+
+        animal.first_feed_age = Some(50);
+
+        let bonus = |generation_end| {
+            let config = Config {
+                generation_end,
+                ..config(FitnessWeights {
+                    food: 0.0,
+                    energy: 0.0,
+                    first_feed: 1.0,
+                    ..Default::default()
+                })
+            };
+
+            AnimalIndividual::from_animal(&animal, &config).fitness()
+        };
+
+        assert_relative_eq!(bonus(GenerationEnd::FixedSteps(100)), 0.5);
+        assert_relative_eq!(bonus(GenerationEnd::FixedSteps(200)), 0.75);
+
+        assert_relative_eq!(
+            bonus(GenerationEnd::FoodConsumedFraction(0.5)),
+            1.0 - 50.0 / GENERATION_LENGTH as f32
+        );
+    }
+
+    #[test]
+    fn concave_transforms_flatten_selection_of_outliers() {
+        use ga::SelectionMethod;
+
+        fn outlier_share(satiation_transform: SatiationTransform) -> f32 {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let config = config(FitnessWeights {
+                energy: 0.0,
+                satiation_transform,
+                ..Default::default()
+            });
+
+            let population: Vec<_> = [20, 1, 1, 1]
+                .into_iter()
+                .map(|satiation| {
+                    let mut animal = Animal::random(&config, &mut rng);
+                    animal.satiation = satiation;
+                    animal.reward = satiation as f32;
+
+                    AnimalIndividual::from_animal(&animal, &config)
+                })
+                .collect();
+
+            let selection = ga::RouletteWheelSelection::new();
+
+            let outliers = selection
+                .select_many(&mut rng, &population, 1000)
+                .into_iter()
+                .filter(|individual| individual.fitness() > 1.0)
+                .count();
+
+            outliers as f32 / 1000.0
+        }
+
+        let linear = outlier_share(SatiationTransform::Linear);
+        let sqrt = outlier_share(SatiationTransform::Sqrt);
+        let log = outlier_share(SatiationTransform::Log);
+
+        // Expected shares are 20/23, sqrt(20)/(sqrt(20)+3) and
+        // ln(21)/(ln(21)+3*ln(2))
+        assert!((linear - 0.87).abs() < 0.03, "{}", linear);
+        assert!((sqrt - 0.6).abs() < 0.03, "{}", sqrt);
+        assert!((log - 0.59).abs() < 0.03, "{}", log);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::*;
+
+/// What happens to animals reaching an edge of the world
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Boundary {
+    /// Animals leaving through one edge come back through the opposite one
+    #[default]
+    Wrap,
+
+    /// Animals stop at the edge
+    Clamp,
+
+    /// Animals bounce off the edge, as if it was a mirror
+    Bounce,
+}
+
+impl Boundary {
+    pub(crate) fn apply(self, animal: &mut Animal) {
+        match self {
+            Self::Wrap => {
+                animal.position.x = na::wrap(animal.position.x, 0.0, 1.0);
+                animal.position.y = na::wrap(animal.position.y, 0.0, 1.0);
+            }
+
+            Self::Clamp => {
+                animal.position.x = animal.position.x.clamp(0.0, 1.0);
+                animal.position.y = animal.position.y.clamp(0.0, 1.0);
+            }
+
+            Self::Bounce => {
+                // Heading is `(-sin(angle), cos(angle))`, so mirroring its x
+                // negates the angle, while mirroring its y yields `PI - angle`
+                let angle = animal.rotation.angle();
+
+                if let Some(x) = Self::reflect(animal.position.x) {
+                    animal.position.x = x;
+                    animal.rotation = na::Rotation2::new(-angle);
+                }
+
+                let angle = animal.rotation.angle();
+
+                if let Some(y) = Self::reflect(animal.position.y) {
+                    animal.position.y = y;
+                    animal.rotation = na::Rotation2::new(PI - angle);
+                }
+            }
+        }
+    }
+
+    /// Mirrors coordinate that's gone past an edge back into `[0, 1]`
+    fn reflect(v: f32) -> Option<f32> {
+        if v < 0.0 {
+            Some((-v).min(1.0))
+        } else if v > 1.0 {
+            Some((2.0 - v).max(0.0))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::f32::consts::FRAC_PI_4;
+
+    fn animal(x: f32, y: f32, rotation: f32) -> Animal {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut animal = Animal::random(&Config::default(), &mut rng);
+
+        animal.position = na::Point2::new(x, y);
+        animal.rotation = na::Rotation2::new(rotation);
+        animal
+    }
+
+    #[test]
+    fn wrap() {
+        let mut animal = animal(1.25, -0.25, 0.0);
+
+        Boundary::Wrap.apply(&mut animal);
+
+        assert_relative_eq!(animal.position.x, 0.25);
+        assert_relative_eq!(animal.position.y, 0.75);
+    }
+
+    #[test]
+    fn clamp() {
+        let mut animal = animal(1.25, -0.25, 0.3);
+
+        Boundary::Clamp.apply(&mut animal);
+
+        assert_relative_eq!(animal.position.x, 1.0);
+        assert_relative_eq!(animal.position.y, 0.0);
+        assert_relative_eq!(animal.rotation.angle(), 0.3);
+    }
+
+    #[test]
+    fn bounce_off_side_wall() {
+        // Heading left & up
+        let mut animal = animal(-0.01, 0.5, FRAC_PI_4);
+
+        Boundary::Bounce.apply(&mut animal);
+
+        assert_relative_eq!(animal.position.x, 0.01);
+        assert_relative_eq!(animal.position.y, 0.5);
+
+        // Heading right & up
+        assert_relative_eq!(animal.rotation.angle(), -FRAC_PI_4);
+    }
+
+    #[test]
+    fn bounce_off_top_wall() {
+        // Heading left & up
+        let mut animal = animal(0.5, 1.01, FRAC_PI_4);
+
+        Boundary::Bounce.apply(&mut animal);
+
+        assert_relative_eq!(animal.position.x, 0.5);
+        assert_relative_eq!(animal.position.y, 0.99);
+
+        // Heading left & down
+        assert_relative_eq!(animal.rotation.angle(), 3.0 * FRAC_PI_4);
+    }
+}
@@ -1,36 +1,421 @@
 use crate::*;
 
+/// Activations applied by brain's layers; since motor outputs get clamped
+/// anyway, a bounded `output` one (e.g. `Tanh`) might train more stably
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BrainActivations {
+    pub hidden: nn::Activation,
+    pub output: nn::Activation,
+}
+
 #[derive(Debug)]
 pub struct Brain {
     pub(crate) nn: nn::Network,
 }
 
 impl Brain {
-    pub fn random(rng: &mut dyn RngCore, eye: &Eye) -> Self {
-        Self {
-            nn: nn::Network::random(rng, &Self::topology(eye)),
-        }
+    pub fn random(
+        rng: &mut dyn RngCore,
+        eye: &Eye,
+        hidden_layers: &[usize],
+        activations: BrainActivations,
+        init: nn::InitStrategy,
+    ) -> Self {
+        let topology = Self::topology(eye, hidden_layers, activations);
+
+        Self::from_network(nn::Network::random_with_init(rng, &topology, init))
     }
 
-    pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
+    /// Wraps given network; it must produce at least [`MotorOutputs::LEN`]
+    /// outputs, since that's what motor models rely on
+    pub fn from_network(nn: nn::Network) -> Self {
+        let outputs = Self::outputs(&nn);
+
+        assert!(
+            outputs >= MotorOutputs::LEN,
+            "network has {} output(s), but brain needs at least {}",
+            outputs,
+            MotorOutputs::LEN,
+        );
+
+        Self { nn }
+    }
+
+    /// Re-randomizes brain's weights, reusing its allocations
+    pub(crate) fn randomize(&mut self, rng: &mut dyn RngCore, init: nn::InitStrategy) {
+        self.nn.randomize(rng, init);
+    }
+
+    /// Returns all of brain's weights (including biases); feeding them back
+    /// into [`Self::from_chromosome()`] yields an identical brain
+    pub fn as_chromosome(&self) -> ga::Chromosome {
         self.nn.weights().collect()
     }
 
-    pub(crate) fn from_chromosome(chromosome: ga::Chromosome, eye: &Eye) -> Self {
-        Self {
-            nn: nn::Network::from_weights(&Self::topology(eye), chromosome.into_iter()),
+    /// Returns weights & biases of each of brain's layers (hidden ones and
+    /// the output one), e.g. for visualizing them
+    pub fn layer_weights(&self) -> Vec<nn::LayerWeights> {
+        self.nn.layer_weights()
+    }
+
+    pub fn from_chromosome(
+        chromosome: ga::Chromosome,
+        eye: &Eye,
+        hidden_layers: &[usize],
+        activations: BrainActivations,
+    ) -> Self {
+        let topology = Self::topology(eye, hidden_layers, activations);
+
+        let nn = nn::Network::from_weights(&topology, chromosome)
+            .unwrap_or_else(|err| panic!("chromosome doesn't match brain's topology: {err}"));
+
+        Self::from_network(nn)
+    }
+
+    /// Returns whether this brain can be driven by given eye and has the
+    /// same topology as brains built from a chromosome would have
+    pub(crate) fn is_compatible_with(
+        &self,
+        eye: &Eye,
+        hidden_layers: &[usize],
+        activations: BrainActivations,
+    ) -> bool {
+        self.nn.topology() == Self::topology(eye, hidden_layers, activations).layers()
+    }
+
+    fn topology(
+        eye: &Eye,
+        hidden_layers: &[usize],
+        activations: BrainActivations,
+    ) -> nn::NetworkTopology {
+        let input = once(nn::LayerTopology::new(eye.inputs()));
+
+        let hidden = hidden_layers
+            .iter()
+            .map(|&neurons| nn::LayerTopology::new(neurons).with_activation(activations.hidden));
+
+        let output =
+            once(nn::LayerTopology::new(MotorOutputs::LEN).with_activation(activations.output));
+
+        nn::NetworkTopology::new(input.chain(hidden).chain(output).collect())
+            .unwrap_or_else(|err| panic!("brain's topology is invalid: {err}"))
+    }
+
+    fn outputs(nn: &nn::Network) -> usize {
+        nn.topology().last().map_or(0, |layer| layer.neurons)
+    }
+
+    /// Returns the number of genes a chromosome has to have to build a brain
+    /// for given eye and hidden layers
+    pub fn required_genes(eye: &Eye, hidden_layers: &[usize]) -> usize {
+        let layers: Vec<_> = once(eye.inputs())
+            .chain(hidden_layers.iter().copied())
+            .chain(once(MotorOutputs::LEN))
+            .collect();
+
+        nn::weight_count(&layers)
+    }
+}
+
+/// Serialized form of a brain: its layer sizes and activations (of all the
+/// layers but the input one), along with its chromosome
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BrainSnapshot {
+    layers: Vec<usize>,
+    activations: Vec<nn::Activation>,
+    chromosome: ga::Chromosome,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Brain {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let topology = self.nn.topology();
+
+        let snapshot = BrainSnapshot {
+            layers: topology.iter().map(|layer| layer.neurons).collect(),
+            activations: topology
+                .iter()
+                .skip(1)
+                .map(|layer| layer.activation)
+                .collect(),
+            chromosome: self.as_chromosome(),
+        };
+
+        snapshot.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Brain {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let snapshot = BrainSnapshot::deserialize(deserializer)?;
+
+        if snapshot.activations.len() + 1 != snapshot.layers.len() {
+            return Err(serde::de::Error::custom(format!(
+                "got {} activation(s) for {} layer(s)",
+                snapshot.activations.len(),
+                snapshot.layers.len()
+            )));
         }
+
+        // Input layer doesn't activate anything, so its activation is moot
+        let activations = once(Default::default()).chain(snapshot.activations);
+
+        let topology: Vec<_> = snapshot
+            .layers
+            .into_iter()
+            .zip(activations)
+            .map(|(neurons, activation)| {
+                nn::LayerTopology::new(neurons).with_activation(activation)
+            })
+            .collect();
+
+        let topology = nn::NetworkTopology::new(topology).map_err(serde::de::Error::custom)?;
+
+        let nn = nn::Network::from_weights(&topology, snapshot.chromosome)
+            .map_err(serde::de::Error::custom)?;
+
+        if Self::outputs(&nn) < MotorOutputs::LEN {
+            return Err(serde::de::Error::custom(format!(
+                "brain needs at least {} outputs",
+                MotorOutputs::LEN
+            )));
+        }
+
+        Ok(Self { nn })
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    const HIDDEN_LAYERS: &[usize] = &[4, 3];
+
+    // (2 * 9 inputs + bias) * 4 + (4 + bias) * 3 + (3 + bias) * 2 outputs
+    const GENES: usize = 99;
+
+    #[test]
+    fn random_brain_with_two_hidden_layers() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        for init in [
+            nn::InitStrategy::Uniform,
+            nn::InitStrategy::Xavier,
+            nn::InitStrategy::He,
+        ] {
+            let brain = Brain::random(
+                &mut rng,
+                &Eye::default(),
+                HIDDEN_LAYERS,
+                Default::default(),
+                init,
+            );
+
+            assert_eq!(brain.as_chromosome().len(), GENES);
+        }
+    }
+
+    #[test]
+    fn from_chromosome_with_two_hidden_layers() {
+        let chromosome = (0..GENES).map(|n| n as f32).collect();
+        let brain = Brain::from_chromosome(
+            chromosome,
+            &Eye::default(),
+            HIDDEN_LAYERS,
+            Default::default(),
+        );
+
+        assert_eq!(brain.as_chromosome().len(), GENES);
+    }
+
+    #[test]
+    fn required_genes() {
+        let eye = Eye::default();
+
+        assert_eq!(Brain::required_genes(&eye, HIDDEN_LAYERS), GENES);
+
+        // (2 * 9 inputs + bias) * 2 outputs
+        assert_eq!(Brain::required_genes(&eye, &[]), 38);
+
+        // (3 * 9 inputs + bias) * 4 + (4 + bias) * 3 + (3 + bias) * 2 outputs
+        let eye = Eye::default().with_sense_animals(true);
+
+        assert_eq!(Brain::required_genes(&eye, HIDDEN_LAYERS), 135);
+    }
+
+    #[test]
+    fn layer_weights_match_chromosome() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let brain = Brain::random(
+            &mut rng,
+            &Eye::default(),
+            HIDDEN_LAYERS,
+            Default::default(),
+            Default::default(),
+        );
+
+        let chromosome: ga::Chromosome = brain
+            .layer_weights()
+            .iter()
+            .flat_map(|layer| {
+                layer
+                    .biases
+                    .iter()
+                    .zip(&layer.weights)
+                    .flat_map(|(bias, weights)| once(bias).chain(weights))
+            })
+            .copied()
+            .collect();
+
+        assert_eq!(chromosome.as_slice(), brain.as_chromosome().as_slice());
+    }
+
+    #[test]
+    fn output_activation_differs_from_hidden_one() {
+        let eye = Eye::default();
+
+        let activations = BrainActivations {
+            hidden: nn::Activation::Relu,
+            output: nn::Activation::Tanh,
+        };
+
+        // Single hidden neuron passing the first input through (bias 0.0,
+        // weight 1.0), with both outputs scaling it by -2.0
+        let mut genes = vec![0.0; eye.inputs() + 1];
+        genes[1] = 1.0;
+        genes.extend([0.0, -2.0, 0.0, -2.0]);
+
+        let brain = Brain::from_chromosome(genes.into_iter().collect(), &eye, &[1], activations);
+
+        let topology = brain.nn.topology();
+        assert_eq!(topology[1].activation, nn::Activation::Relu);
+        assert_eq!(topology[2].activation, nn::Activation::Tanh);
+
+        let mut inputs = vec![0.0; eye.inputs()];
+        inputs[0] = 1.0;
+
+        // ReLU would've zeroed the negative outputs, tanh keeps them bounded
+        let expected = (-2.0f32).tanh();
+        assert_eq!(brain.nn.propagate(inputs), [expected, expected]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_keeps_activations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let activations = BrainActivations {
+            hidden: nn::Activation::LeakyRelu,
+            output: nn::Activation::Tanh,
+        };
+
+        let brain = Brain::random(
+            &mut rng,
+            &Eye::default(),
+            HIDDEN_LAYERS,
+            activations,
+            Default::default(),
+        );
+
+        let restored: Brain =
+            serde_json::from_str(&serde_json::to_string(&brain).unwrap()).unwrap();
+
+        assert!(restored.is_compatible_with(&Eye::default(), HIDDEN_LAYERS, activations));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_mismatched_activations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let brain = Brain::random(
+            &mut rng,
+            &Eye::default(),
+            HIDDEN_LAYERS,
+            Default::default(),
+            Default::default(),
+        );
+
+        let mut snapshot = serde_json::to_value(&brain).unwrap();
+
+        assert!(serde_json::from_value::<Brain>(snapshot.clone()).is_ok());
+
+        snapshot["activations"].as_array_mut().unwrap().pop();
+
+        assert!(serde_json::from_value::<Brain>(snapshot).is_err());
+    }
+
+    #[test]
+    fn chromosome_round_trip() {
+        let eye = Eye::default();
+        let inputs: Vec<_> = (0..eye.inputs()).map(|n| n as f32 / 10.0).collect();
+
+        for seed in 0..32 {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let hidden_layers = [rng.gen_range(1..8), rng.gen_range(1..8)];
+
+            let brain = Brain::random(
+                &mut rng,
+                &eye,
+                &hidden_layers,
+                Default::default(),
+                Default::default(),
+            );
+            let restored = Brain::from_chromosome(
+                brain.as_chromosome(),
+                &eye,
+                &hidden_layers,
+                Default::default(),
+            );
+
+            assert_eq!(
+                brain.nn.weights().collect::<Vec<_>>(),
+                restored.nn.weights().collect::<Vec<_>>()
+            );
+
+            assert_eq!(brain.nn.topology(), restored.nn.topology());
+
+            assert_eq!(
+                brain.nn.propagate(inputs.clone()),
+                restored.nn.propagate(inputs.clone())
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "chromosome doesn't match brain's topology")]
+    fn from_chromosome_with_too_few_genes() {
+        let chromosome = (0..GENES - 1).map(|n| n as f32).collect();
+
+        Brain::from_chromosome(
+            chromosome,
+            &Eye::default(),
+            HIDDEN_LAYERS,
+            Default::default(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "network has 1 output(s), but brain needs at least 2")]
+    fn from_network_with_too_few_outputs() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let topology =
+            nn::NetworkTopology::new(vec![nn::LayerTopology::new(4), nn::LayerTopology::new(1)])
+                .unwrap();
+
+        let nn = nn::Network::random(&mut rng, &topology);
 
-    fn topology(eye: &Eye) -> [nn::LayerTopology; 3] {
-        [
-            nn::LayerTopology {
-                neurons: eye.cells(),
-            },
-            nn::LayerTopology {
-                neurons: 2 * eye.cells(),
-            },
-            nn::LayerTopology { neurons: 2 },
-        ]
+        Brain::from_network(nn);
     }
 }
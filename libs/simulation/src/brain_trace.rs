@@ -0,0 +1,17 @@
+/// Everything that goes through animal's brain during a single step, as
+/// returned by [`Animal::debug_step()`](crate::Animal::debug_step)
+#[derive(Clone, Debug, PartialEq)]
+pub struct BrainTrace {
+    /// Brain's inputs, as laid out by [`Eye::process_vision()`](crate::Eye::process_vision)
+    pub vision: Vec<f32>,
+
+    /// Brain's raw outputs, before replacing non-finite ones with zeros
+    pub outputs: Vec<f32>,
+
+    /// Change of speed the motor model would apply, after clamping
+    pub speed_delta: f32,
+
+    /// Change of rotation (in radians) the motor model would apply, after
+    /// clamping
+    pub rotation_delta: f32,
+}
@@ -0,0 +1,275 @@
+use crate::*;
+use std::sync::Arc;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SimulationMode {
+    /// Animals only compete for food
+    #[default]
+    SingleSpecies,
+
+    /// Animals (prey) are additionally hunted by predators, which evolve
+    /// separately and see prey instead of food; prey, in turn, see predators
+    /// through an additional vision channel.
+    ///
+    /// Predators evolve through the default genetic algorithm, unless told
+    /// otherwise via [`Simulation::with_predator_ga()`]
+    PredatorPrey,
+}
+
+/// What happens to food after it gets eaten
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FoodPolicy {
+    /// Food immediately reappears somewhere else
+    #[default]
+    Respawn,
+
+    /// Food reappears somewhere else after given number of steps
+    Timed { delay: usize },
+
+    /// Food is gone for good, until the next generation; running out of food
+    /// ends the generation early
+    Finite,
+}
+
+/// Which foods an animal eats when it's within range of a few of them
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EatMode {
+    /// Animal eats all of them at once
+    #[default]
+    All,
+
+    /// Animal eats only the nearest one, leaving the rest for later steps
+    NearestOne,
+}
+
+/// How much a food is worth, given how many foods the animal has eaten so far
+/// (i.e. its satiation); see [`Animal::reward()`]
+#[derive(Clone)]
+pub struct RewardFn(Arc<dyn Fn(u32) -> f32 + Send + Sync>);
+
+impl RewardFn {
+    pub fn new(f: impl Fn(u32) -> f32 + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Each food is worth the same
+    pub fn constant(reward: f32) -> Self {
+        Self::new(move |_| reward)
+    }
+
+    pub fn reward(&self, satiation: u32) -> f32 {
+        (self.0)(satiation)
+    }
+}
+
+impl Default for RewardFn {
+    fn default() -> Self {
+        Self::constant(1.0)
+    }
+}
+
+impl fmt::Debug for RewardFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RewardFn")
+    }
+}
+
+/// When a generation ends (apart from running out of food under
+/// [`FoodPolicy::Finite`], which always ends it)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GenerationEnd {
+    /// After given number of steps
+    FixedSteps(usize),
+
+    /// Once given fraction of the generation's foods has been eaten (eating
+    /// a respawned food counts again); a generation without any foods ends
+    /// right away, since there's nothing to eat
+    FoodConsumedFraction(f32),
+
+    /// After given number of steps or once given fraction of foods has been
+    /// eaten, whichever comes first (i.e. right away without any foods, too)
+    Either { steps: usize, fraction: f32 },
+}
+
+impl GenerationEnd {
+    /// Returns the maximum number of steps a generation can last, if any
+    pub fn steps(self) -> Option<usize> {
+        match self {
+            Self::FixedSteps(steps) | Self::Either { steps, .. } => Some(steps),
+            Self::FoodConsumedFraction(_) => None,
+        }
+    }
+
+    pub(crate) fn has_ended(self, age: usize, foods_eaten: usize, n_foods: usize) -> bool {
+        let consumed =
+            |fraction: f32| n_foods == 0 || foods_eaten as f32 >= fraction * n_foods as f32;
+
+        match self {
+            Self::FixedSteps(steps) => age > steps,
+            Self::FoodConsumedFraction(fraction) => consumed(fraction),
+            Self::Either { steps, fraction } => age > steps || consumed(fraction),
+        }
+    }
+}
+
+impl Default for GenerationEnd {
+    fn default() -> Self {
+        Self::FixedSteps(GENERATION_LENGTH)
+    }
+}
+
+/// How the number of foods changes over generations
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FoodSchedule {
+    /// There's always [`Config::n_foods`] foods
+    #[default]
+    Constant,
+
+    /// Number of foods follows a sine wave between `min` and `max`, repeating
+    /// every `period` generations; it starts half way between them and rises
+    /// first (i.e. the first generation is an early spring)
+    Sinusoidal {
+        min: usize,
+        max: usize,
+        period: usize,
+    },
+}
+
+impl FoodSchedule {
+    /// Returns number of foods for given generation, where `n_foods` is what
+    /// [`Self::Constant`] yields
+    pub fn n_foods(self, n_foods: usize, generation: usize) -> usize {
+        match self {
+            Self::Constant => n_foods,
+
+            Self::Sinusoidal { min, max, period } => {
+                assert!(min <= max);
+                assert!(period > 0);
+
+                let phase = (generation % period) as f32 / period as f32;
+                let wave = (1.0 + (phase * std::f32::consts::TAU).sin()) / 2.0;
+
+                min + ((max - min) as f32 * wave).round() as usize
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub mode: SimulationMode,
+
+    pub n_animals: usize,
+    /// Only used in [`SimulationMode::PredatorPrey`]
+    pub n_predators: usize,
+    pub n_foods: usize,
+    pub food_schedule: FoodSchedule,
+    pub n_obstacles: usize,
+    pub obstacle_radius: f32,
+
+    pub boundary: Boundary,
+
+    /// Whether animals of the same species get pushed apart when they
+    /// overlap, instead of passing through one another
+    pub collision_enabled: bool,
+    pub animal_radius: f32,
+
+    pub eye_fov_range: f32,
+    pub eye_fov_angle: f32,
+
+    /// Number of photoreceptors; each one feeds a brain input per kind of food
+    pub eye_cells: usize,
+
+    /// Whether eyes see other animals (of the same kind) as well
+    pub eye_sense_animals: bool,
+
+    /// Whether eyes see across edges of the world, when it wraps around
+    pub toroidal_vision: bool,
+
+    /// Number of neurons in each of the brain's hidden layers; input & output
+    /// layers are sized automatically, basing on the eye and motor outputs
+    pub brain_hidden_layers: Vec<usize>,
+
+    pub brain_activations: BrainActivations,
+
+    /// How brains of the initial, random population get their weights
+    pub brain_init: nn::InitStrategy,
+
+    pub motor_model: MotorModel,
+
+    /// Probability of a newly created food being poisonous
+    pub food_poison_chance: f32,
+
+    pub food_policy: FoodPolicy,
+
+    pub eat_mode: EatMode,
+
+    pub generation_end: GenerationEnd,
+
+    /// Animals moving at most this fast count as idle, which gets penalized
+    /// as per [`FitnessWeights::idle`]
+    pub idle_speed: f32,
+
+    /// How close an animal has to get to a food to eat it; see
+    /// [`Animal::with_radius()`] for overriding it per animal
+    pub food_collision_radius: f32,
+
+    /// Reward for each (good) food eaten, which determines how much the food
+    /// contributes to animal's fitness
+    pub reward_fn: RewardFn,
+
+    /// If set, foods get placed using a separate rng seeded with this value,
+    /// so that their layout doesn't depend on e.g. the genetic algorithm's
+    /// settings
+    pub food_seed: Option<u64>,
+
+    /// If set, the arena gets split into `n × n` cells and the simulation
+    /// keeps track of which of them animals visit during a generation, see
+    /// [`Simulation::coverage()`]
+    pub coverage_cells: Option<usize>,
+
+    pub fitness_weights: FitnessWeights,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            mode: SimulationMode::default(),
+            n_animals: 40,
+            n_predators: 10,
+            n_foods: 40,
+            food_schedule: FoodSchedule::default(),
+            n_obstacles: 0,
+            obstacle_radius: 0.05,
+            boundary: Boundary::default(),
+            collision_enabled: false,
+            animal_radius: 0.01,
+            eye_fov_range: eye::FOV_RANGE,
+            eye_fov_angle: eye::FOV_ANGLE,
+            eye_cells: eye::CELLS,
+            eye_sense_animals: false,
+            toroidal_vision: false,
+            brain_hidden_layers: vec![2 * eye::CELLS],
+            brain_activations: BrainActivations::default(),
+            brain_init: nn::InitStrategy::default(),
+            motor_model: MotorModel::default(),
+            food_poison_chance: 0.0,
+            food_policy: FoodPolicy::default(),
+            eat_mode: EatMode::default(),
+            generation_end: GenerationEnd::default(),
+            idle_speed: SPEED_MIN,
+            food_collision_radius: EAT_DISTANCE,
+            reward_fn: RewardFn::default(),
+            food_seed: None,
+            coverage_cells: None,
+            fitness_weights: FitnessWeights::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Returns number of foods for given generation, as per the schedule
+    pub fn n_foods_at(&self, generation: usize) -> usize {
+        self.food_schedule.n_foods(self.n_foods, generation)
+    }
+}
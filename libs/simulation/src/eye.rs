@@ -1,19 +1,25 @@
 use crate::*;
 use std::f32::consts::*;
 
-const FOV_RANGE: f32 = 0.25;
-const FOV_ANGLE: f32 = PI + FRAC_PI_4;
-const CELLS: usize = 9;
+pub(crate) const FOV_RANGE: f32 = 0.25;
+pub(crate) const FOV_ANGLE: f32 = PI + FRAC_PI_4;
+pub(crate) const CELLS: usize = 9;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Eye {
     fov_range: f32,
     fov_angle: f32,
     cells: usize,
+    sense_animals: bool,
+    sense_predators: bool,
+    toroidal: bool,
 }
 
 impl Eye {
-    fn new(fov_range: f32, fov_angle: f32, cells: usize) -> Self {
+    /// Note that `cells` determines the size of the brain's input layer, so
+    /// brains have to be (re)built to match the eye they're paired with.
+    pub fn new(fov_range: f32, fov_angle: f32, cells: usize) -> Self {
         assert!(fov_range > 0.0);
         assert!(fov_angle > 0.0);
         assert!(cells > 0);
@@ -22,49 +28,176 @@ impl Eye {
             fov_range,
             fov_angle,
             cells,
+            sense_animals: false,
+            sense_predators: false,
+            toroidal: false,
         }
     }
 
+    /// Makes the eye see other animals too, through an additional channel
+    /// (which adds another `cells` inputs to the brain)
+    pub fn with_sense_animals(mut self, sense_animals: bool) -> Self {
+        self.sense_animals = sense_animals;
+        self
+    }
+
+    pub fn fov_range(&self) -> f32 {
+        self.fov_range
+    }
+
+    pub fn fov_angle(&self) -> f32 {
+        self.fov_angle
+    }
+
     pub fn cells(&self) -> usize {
         self.cells
     }
 
+    pub fn sense_animals(&self) -> bool {
+        self.sense_animals
+    }
+
+    /// Makes the eye see predators too, through yet another channel (coming
+    /// after the animal one, if any)
+    pub fn with_sense_predators(mut self, sense_predators: bool) -> Self {
+        self.sense_predators = sense_predators;
+        self
+    }
+
+    pub fn sense_predators(&self) -> bool {
+        self.sense_predators
+    }
+
+    /// Makes the eye measure distances and angles the shortest way around a
+    /// wrapping world (see [`Boundary::Wrap`]), so that e.g. food lying just
+    /// past the opposite edge is seen as being close
+    pub fn with_toroidal(mut self, toroidal: bool) -> Self {
+        self.toroidal = toroidal;
+        self
+    }
+
+    pub fn toroidal(&self) -> bool {
+        self.toroidal
+    }
+
+    /// Number of values returned by `process_vision`, i.e. one per cell per
+    /// kind of food (plus one per cell for animals and predators, if they're
+    /// sensed)
+    pub fn inputs(&self) -> usize {
+        self.cells * self.channels()
+    }
+
+    fn channels(&self) -> usize {
+        FoodKind::ALL.len() + usize::from(self.sense_animals) + usize::from(self.sense_predators)
+    }
+
+    /// Returns the angular span `(start, end)` of each cell, relative to the
+    /// heading and in the same order as cells of `process_vision`'s output
+    pub fn cell_angles(&self) -> Vec<(f32, f32)> {
+        let width = self.fov_angle / self.cells as f32;
+        let start = -self.fov_angle / 2.0;
+
+        (0..self.cells)
+            .map(|cell| {
+                (
+                    start + width * cell as f32,
+                    start + width * (cell + 1) as f32,
+                )
+            })
+            .collect()
+    }
+
+    /// `animals` are positions of the other animals (i.e. without the one
+    /// looking); they're ignored unless the eye senses animals - and the same
+    /// goes for `predators`
     pub fn process_vision(
         &self,
         position: na::Point2<f32>,
         rotation: na::Rotation2<f32>,
         foods: &[Food],
+        animals: &[na::Point2<f32>],
+        predators: &[na::Point2<f32>],
+        obstacles: &[Obstacle],
     ) -> Vec<f32> {
-        let mut cells = vec![0.0; self.cells];
+        // Each kind of food gets its own channel - i.e. first come all cells
+        // seeing good food, then all cells seeing poison and so on, with
+        // animals and predators coming last
+        let mut cells = vec![0.0; self.inputs()];
 
         for food in foods {
-            let vec = food.position - position;
-            let dist = vec.norm();
-
-            if dist >= self.fov_range {
-                continue;
+            if let Some((cell, energy)) = self.see(position, rotation, food.position, obstacles) {
+                cells[food.kind.channel() * self.cells + cell] += energy;
             }
+        }
 
-            let angle = na::Rotation2::rotation_between(&na::Vector2::y(), &vec).angle();
-            let angle = angle - rotation.angle();
-            let angle = na::wrap(angle, -PI, PI);
+        let mut channel = FoodKind::ALL.len();
 
-            if angle < -self.fov_angle / 2.0 || angle > self.fov_angle / 2.0 {
+        for (sensed, targets) in [
+            (self.sense_animals, animals),
+            (self.sense_predators, predators),
+        ] {
+            if !sensed {
                 continue;
             }
 
-            let angle = angle + self.fov_angle / 2.0;
-            let cell = angle / self.fov_angle;
+            for &target in targets {
+                if let Some((cell, energy)) = self.see(position, rotation, target, obstacles) {
+                    cells[channel * self.cells + cell] += energy;
+                }
+            }
+
+            channel += 1;
+        }
+
+        cells
+    }
+
+    /// Returns which cell sees given target and how strongly, if any
+    fn see(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+        target: na::Point2<f32>,
+        obstacles: &[Obstacle],
+    ) -> Option<(usize, f32)> {
+        let mut vec = target - position;
 
-            let cell = cell * (self.cells as f32);
-            let cell = (cell as usize).min(cells.len() - 1);
+        if self.toroidal {
+            vec.x -= vec.x.round();
+            vec.y -= vec.y.round();
+        }
 
-            let energy = (self.fov_range - dist) / self.fov_range;
+        let target = position + vec;
+        let dist = vec.norm();
 
-            cells[cell] += energy;
+        if dist >= self.fov_range {
+            return None;
         }
 
-        cells
+        if obstacles
+            .iter()
+            .any(|obstacle| obstacle.blocks(position, target))
+        {
+            return None;
+        }
+
+        let angle = na::Rotation2::rotation_between(&na::Vector2::y(), &vec).angle();
+        let angle = angle - rotation.angle();
+        let angle = na::wrap(angle, -PI, PI);
+
+        if angle < -self.fov_angle / 2.0 || angle > self.fov_angle / 2.0 {
+            return None;
+        }
+
+        let angle = angle + self.fov_angle / 2.0;
+        let cell = angle / self.fov_angle;
+
+        let cell = cell * (self.cells as f32);
+        let cell = (cell as usize).min(self.cells - 1);
+
+        let energy = (self.fov_range - dist) / self.fov_range;
+
+        Some((cell, energy))
     }
 }
 
@@ -74,11 +207,21 @@ impl Default for Eye {
     }
 }
 
+impl From<&Config> for Eye {
+    fn from(config: &Config) -> Self {
+        Self::new(config.eye_fov_range, config.eye_fov_angle, config.eye_cells)
+            .with_sense_animals(config.eye_sense_animals)
+            .with_sense_predators(config.mode == SimulationMode::PredatorPrey)
+            .with_toroidal(config.toroidal_vision && config.boundary == Boundary::Wrap)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::vec;
 
     use super::*;
+    use approx::assert_relative_eq;
     use test_case::test_case;
 
     struct TestCase {
@@ -100,32 +243,39 @@ mod tests {
                 na::Point2::new(self.x, self.y),
                 na::Rotation2::new(self.rot),
                 &self.foods,
+                &[],
+                &[],
+                &[],
             );
 
-            let actual_vision: Vec<_> = actual_vision
-                .into_iter()
-                .map(|cell| {
-                    if cell >= 0.7 {
-                        "#"
-                    } else if cell >= 0.3 {
-                        "+"
-                    } else if cell > 0.0 {
-                        "."
-                    } else {
-                        " "
-                    }
-                })
-                .collect();
-
-            let actual_vision = actual_vision.join("");
+            let (good_vision, poison_vision) = actual_vision.split_at(TEST_EYE_CELLS);
 
-            assert_eq!(actual_vision, self.expected_vision);
+            assert_eq!(render(good_vision), self.expected_vision);
+            assert_eq!(render(poison_vision), " ".repeat(TEST_EYE_CELLS));
         }
     }
 
+    fn render(vision: &[f32]) -> String {
+        vision
+            .iter()
+            .map(|&cell| {
+                if cell >= 0.7 {
+                    "#"
+                } else if cell >= 0.3 {
+                    "+"
+                } else if cell > 0.0 {
+                    "."
+                } else {
+                    " "
+                }
+            })
+            .collect()
+    }
+
     fn food(x: f32, y: f32) -> Food {
         Food {
             position: na::Point2::new(x, y),
+            kind: FoodKind::Good,
         }
     }
 
@@ -243,4 +393,200 @@ mod tests {
         }
         .run()
     }
+
+    #[test_case(FRAC_PI_4, "#            ")] // Food sits exactly on the left edge
+    #[test_case(-FRAC_PI_4, "            #")] // ... and on the right edge
+    fn fov_edges(rot: f32, expected_vision: &'static str) {
+        TestCase {
+            foods: vec![food(0.5, 0.6)],
+            fov_range: 1.0,
+            fov_angle: FRAC_PI_2,
+            x: 0.5,
+            y: 0.5,
+            rot,
+            expected_vision,
+        }
+        .run()
+    }
+
+    #[test]
+    fn food_behind() {
+        TestCase {
+            foods: vec![food(0.5, 0.4)],
+            fov_range: 1.0,
+            fov_angle: PI + FRAC_PI_2,
+            x: 0.5,
+            y: 0.5,
+            rot: 0.0,
+            expected_vision: "             ",
+        }
+        .run()
+    }
+
+    #[test]
+    fn poison_is_seen_only_through_poison_channel() {
+        let eye = Eye::new(1.0, FRAC_PI_2, TEST_EYE_CELLS);
+
+        let foods = [Food {
+            position: na::Point2::new(0.5, 1.0),
+            kind: FoodKind::Poison,
+        }];
+
+        let vision = eye.process_vision(
+            na::Point2::new(0.5, 0.5),
+            na::Rotation2::new(0.0),
+            &foods,
+            &[],
+            &[],
+            &[],
+        );
+        let (good_vision, poison_vision) = vision.split_at(TEST_EYE_CELLS);
+
+        assert_eq!(render(good_vision), "             ");
+        assert_eq!(render(poison_vision), "      +      ");
+    }
+
+    #[test]
+    fn cell_angles_partition_fov() {
+        let eye = Eye::new(1.0, FOV_ANGLE, TEST_EYE_CELLS);
+        let angles = eye.cell_angles();
+
+        assert_eq!(angles.len(), TEST_EYE_CELLS);
+        assert_eq!(angles[0].0, -FOV_ANGLE / 2.0);
+        assert_relative_eq!(angles[TEST_EYE_CELLS - 1].1, FOV_ANGLE / 2.0);
+
+        for pair in angles.windows(2) {
+            assert_eq!(pair[0].1, pair[1].0);
+        }
+
+        for (start, end) in angles {
+            assert_relative_eq!(end - start, FOV_ANGLE / TEST_EYE_CELLS as f32);
+        }
+    }
+
+    #[test]
+    fn food_behind_obstacle_is_occluded() {
+        let eye = Eye::new(1.0, FRAC_PI_2, TEST_EYE_CELLS);
+        let foods = [food(0.5, 1.0), food(0.6, 0.9)];
+        let obstacles = [Obstacle::new(na::Point2::new(0.5, 0.75), 0.05)];
+
+        let vision = eye.process_vision(
+            na::Point2::new(0.5, 0.5),
+            na::Rotation2::new(0.0),
+            &foods,
+            &[],
+            &[],
+            &obstacles,
+        );
+
+        // Food straight ahead is hidden, the one to the side is not
+        assert_eq!(render(&vision[..TEST_EYE_CELLS]), "    +        ");
+    }
+
+    #[test]
+    fn animal_is_seen_only_through_animal_channel() {
+        let eye = Eye::new(1.0, FRAC_PI_2, TEST_EYE_CELLS).with_sense_animals(true);
+
+        let vision = eye.process_vision(
+            na::Point2::new(0.5, 0.5),
+            na::Rotation2::new(0.0),
+            &[],
+            &[na::Point2::new(0.5, 1.0)],
+            &[],
+            &[],
+        );
+
+        assert_eq!(vision.len(), 3 * TEST_EYE_CELLS);
+
+        let (food_vision, animal_vision) = vision.split_at(2 * TEST_EYE_CELLS);
+
+        assert_eq!(render(food_vision), " ".repeat(2 * TEST_EYE_CELLS));
+        assert_eq!(render(animal_vision), "      +      ");
+    }
+
+    #[test]
+    fn toroidal_eye_sees_across_edge() {
+        let vision = |toroidal| {
+            let eye = Eye::new(0.25, FRAC_PI_2, TEST_EYE_CELLS).with_toroidal(toroidal);
+
+            // Looking towards the right edge, with food just past it
+            let vision = eye.process_vision(
+                na::Point2::new(0.99, 0.5),
+                na::Rotation2::new(-FRAC_PI_2),
+                &[food(0.01, 0.5)],
+                &[],
+                &[],
+                &[],
+            );
+
+            render(&vision[..TEST_EYE_CELLS])
+        };
+
+        assert_eq!(vision(false), "             ");
+        assert_eq!(vision(true), "      #      ");
+    }
+
+    #[test]
+    fn toroidal_eye_follows_config() {
+        let config = |boundary, toroidal_vision| Config {
+            boundary,
+            toroidal_vision,
+            ..Default::default()
+        };
+
+        assert!(Eye::from(&config(Boundary::Wrap, true)).toroidal());
+        assert!(!Eye::from(&config(Boundary::Wrap, false)).toroidal());
+        assert!(!Eye::from(&config(Boundary::Clamp, true)).toroidal());
+    }
+
+    #[test]
+    fn animals_are_ignored_unless_sensed() {
+        let eye = Eye::new(1.0, FRAC_PI_2, TEST_EYE_CELLS);
+
+        let vision = eye.process_vision(
+            na::Point2::new(0.5, 0.5),
+            na::Rotation2::new(0.0),
+            &[],
+            &[na::Point2::new(0.5, 1.0)],
+            &[],
+            &[],
+        );
+
+        assert_eq!(vision.len(), 2 * TEST_EYE_CELLS);
+        assert!(vision.iter().all(|&cell| cell == 0.0));
+    }
+
+    #[test]
+    fn predator_is_seen_only_through_predator_channel() {
+        let eye = Eye::new(1.0, FRAC_PI_2, TEST_EYE_CELLS)
+            .with_sense_animals(true)
+            .with_sense_predators(true);
+
+        let vision = eye.process_vision(
+            na::Point2::new(0.5, 0.5),
+            na::Rotation2::new(0.0),
+            &[],
+            &[],
+            &[na::Point2::new(0.5, 1.0)],
+            &[],
+        );
+
+        assert_eq!(vision.len(), 4 * TEST_EYE_CELLS);
+
+        let (other_vision, predator_vision) = vision.split_at(3 * TEST_EYE_CELLS);
+
+        assert_eq!(render(other_vision), " ".repeat(3 * TEST_EYE_CELLS));
+        assert_eq!(render(predator_vision), "      +      ");
+    }
+
+    #[test]
+    fn predators_are_sensed_in_predator_prey_mode() {
+        let config = |mode| Config {
+            mode,
+            ..Default::default()
+        };
+
+        assert!(Eye::from(&config(SimulationMode::PredatorPrey)).sense_predators());
+        assert!(!Eye::from(&config(SimulationMode::SingleSpecies)).sense_predators());
+    }
 }
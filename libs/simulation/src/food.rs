@@ -1,18 +1,50 @@
 use crate::*;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FoodKind {
+    Good,
+    Poison,
+}
+
+impl FoodKind {
+    pub const ALL: [FoodKind; 2] = [FoodKind::Good, FoodKind::Poison];
+
+    /// Index of the eye channel this kind of food is seen through
+    pub(crate) fn channel(self) -> usize {
+        match self {
+            Self::Good => 0,
+            Self::Poison => 1,
+        }
+    }
+}
+
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Food {
     pub(crate) position: na::Point2<f32>,
+    pub(crate) kind: FoodKind,
 }
 
 impl Food {
-    pub fn random(rng: &mut dyn RngCore) -> Self {
+    pub fn random(config: &Config, rng: &mut dyn RngCore) -> Self {
+        let kind = if rng.gen_bool(config.food_poison_chance as f64) {
+            FoodKind::Poison
+        } else {
+            FoodKind::Good
+        };
+
         Self {
             position: rng.gen(),
+            kind,
         }
     }
 
     pub fn position(&self) -> na::Point2<f32> {
         self.position
     }
+
+    pub fn kind(&self) -> FoodKind {
+        self.kind
+    }
 }
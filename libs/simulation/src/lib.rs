@@ -1,62 +1,411 @@
 mod animal;
 mod animal_individual;
+mod boundary;
 mod brain;
+mod brain_trace;
+mod config;
 mod eye;
 mod food;
+mod motor;
+mod obstacle;
+mod spatial_grid;
+mod statistics;
+mod statistics_logger;
+mod step_report;
 mod world;
 
 pub use self::animal_individual::*;
-pub use self::{animal::*, brain::*, eye::*, food::*, world::*};
+use self::spatial_grid::*;
+pub use self::{
+    animal::*, boundary::*, brain::*, brain_trace::*, config::*, eye::*, food::*, motor::*,
+    obstacle::*, statistics::*, statistics_logger::*, step_report::*, world::*,
+};
 
 use lib_genetic_algorithm as ga;
 use lib_neural_network as nn;
 use nalgebra as na;
-use rand::{Rng, RngCore};
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::error::Error;
+use std::fmt;
+use std::iter::once;
 
-use std::f32::consts::FRAC_PI_2;
+use std::f32::consts::{FRAC_PI_2, PI};
 const SPEED_MIN: f32 = 0.001;
 const SPEED_MAX: f32 = 0.005;
 const SPEED_ACCEL: f32 = 0.2;
 const ROTATION_ACCEL: f32 = FRAC_PI_2;
 const GENERATION_LENGTH: usize = 2500;
+const ENERGY_MAX: f32 = 1.0;
+const ENERGY_DECAY: f32 = 0.1;
+const ENERGY_PER_FOOD: f32 = 0.25;
+const EAT_DISTANCE: f32 = 0.01;
+const CATCH_DISTANCE: f32 = 0.01;
 
-pub struct Simulation {
+/// Number of steps each animal gets to show its behavior, see
+/// [`Simulation::behavioral_diversity()`]
+const BEHAVIOR_HORIZON: usize = 100;
+
+pub struct Simulation<S = ga::RouletteWheelSelection> {
+    config: Config,
     world: World,
-    ga: ga::GeneticAlgorithm<ga::RouletteWheelSelection>,
-    age: usize,
+    ga: ga::GeneticAlgorithm<S>,
+    predator_ga: ga::GeneticAlgorithm<ga::RouletteWheelSelection>,
+    generation: usize,
+    best_ever: Option<(ga::Chromosome, usize)>,
+
+    /// Steps left until each of the foods eaten under
+    /// [`FoodPolicy::Timed`] reappears
+    food_timers: Vec<usize>,
+
+    /// Used by the `*_seeded()` methods
+    rng: ChaCha8Rng,
+
+    /// If set, the `*_seeded()` methods reseed `rng` at the beginning of
+    /// each generation, see [`Self::resume_seeded()`]
+    base_seed: Option<u64>,
+
+    /// Used for scattering foods at the beginning of each generation, if
+    /// [`Config::food_seed`] is set; reseeded with each generation
+    food_rng: Option<ChaCha8Rng>,
+
+    /// Used for placing foods that reappear during a generation, if
+    /// [`Config::food_seed`] is set; kept apart from `food_rng`, since how
+    /// much gets eaten depends on the brains
+    respawn_rng: Option<ChaCha8Rng>,
+
+    /// Number of sanitized brain outputs during the current generation
+    non_finite_outputs: usize,
+
+    /// Number of foods eaten during the current generation
+    foods_eaten: usize,
+
+    /// See [`Self::set_evolution_enabled()`]
+    evolution_enabled: bool,
+
+    /// How many times animals have been to each of the cells during the
+    /// current generation, if [`Config::coverage_cells`] is set
+    visits: Vec<usize>,
 }
 
 impl Simulation {
-    pub fn random(rng: &mut dyn RngCore) -> Self {
-        let world = World::random(rng);
+    pub fn random(config: Config, rng: &mut dyn RngCore) -> Self {
+        let world = World::random(&config, rng);
 
-        let ga = ga::GeneticAlgorithm::new(
-            ga::RouletteWheelSelection,
-            ga::UniformCrossover,
-            ga::GaussianMutation::new(0.01, 0.3),
-        );
+        Self::from_world(config, world)
+    }
+
+    /// Creates a simulation starting from given animals (e.g. a population
+    /// that has already been trained), with foods & co. being random
+    pub fn from_animals(
+        animals: Vec<Animal>,
+        config: Config,
+        rng: &mut dyn RngCore,
+    ) -> Result<Self, PopulationError> {
+        if animals.is_empty() {
+            return Err(PopulationError::Empty);
+        }
+
+        if let Some(index) = animals
+            .iter()
+            .position(|animal| !animal.is_compatible_with(&config))
+        {
+            return Err(PopulationError::IncompatibleAnimal { index });
+        }
+
+        let world = World::from_animals(&config, animals, rng);
+
+        Ok(Self::from_world(config, world))
+    }
+
+    /// Creates a simulation that owns its randomness, so that it can be
+    /// driven through [`Self::step_seeded()`] and [`Self::train_seeded()`];
+    /// the same seed always yields the same simulation
+    pub fn from_seed(seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let config = Config::default();
+        let world = World::random(&config, &mut rng);
+
+        Self::resume_seeded(config, world, seed, 0)
+    }
+
+    /// Resumes a simulation created through [`Self::from_seed()`] (with
+    /// given config, in case it's been changed since), given its world as of
+    /// the beginning of given generation; since randomness of each
+    /// generation depends just on the seed and generation's index, the
+    /// resumed simulation continues exactly like the original one would.
+    ///
+    /// State kept by the genetic algorithm (e.g. by an adaptive mutation
+    /// method) is not restored, though - the default one doesn't have any.
+    pub fn resume_seeded(config: Config, world: World, seed: u64, generation: usize) -> Self {
+        let mut sim = Self::from_world(config, world);
+
+        sim.generation = generation;
+        sim.base_seed = Some(seed);
+        sim.reseed();
+        sim.reseed_foods();
+        sim
+    }
+
+    /// Resumes simulation from given world, e.g. one restored from a snapshot
+    pub fn from_world(config: Config, world: World) -> Self {
+        Self::with_ga(world, default_ga(), config)
+    }
+}
+
+impl<S> Simulation<S>
+where
+    S: ga::SelectionMethod,
+{
+    /// Creates a simulation evolving animals through given genetic algorithm
+    /// (predators, if any, use the default one - see
+    /// [`Self::with_predator_ga()`])
+    pub fn with_ga(world: World, ga: ga::GeneticAlgorithm<S>, config: Config) -> Self {
+        config.motor_model.validate();
 
-        Self { world, ga, age: 0 }
+        let mut sim = Self {
+            world,
+            ga,
+            predator_ga: default_ga(),
+            generation: 0,
+            best_ever: None,
+            food_timers: Vec::new(),
+            rng: ChaCha8Rng::seed_from_u64(0),
+            base_seed: None,
+            food_rng: None,
+            respawn_rng: None,
+            non_finite_outputs: 0,
+            foods_eaten: 0,
+            evolution_enabled: true,
+            visits: vec![0; config.coverage_cells.map_or(0, |n| n * n)],
+            config,
+        };
+
+        sim.reseed_foods();
+        sim
+    }
+
+    /// Makes predators evolve through given genetic algorithm instead of the
+    /// default one
+    pub fn with_predator_ga(
+        mut self,
+        ga: ga::GeneticAlgorithm<ga::RouletteWheelSelection>,
+    ) -> Self {
+        self.predator_ga = ga;
+        self
     }
 
     pub fn world(&self) -> &World {
         &self.world
     }
 
-    pub fn step(&mut self, rng: &mut dyn RngCore) -> Option<ga::Statistics> {
-        self.process_collisions(rng);
+    /// Number of steps taken in the current generation
+    pub fn age(&self) -> usize {
+        self.world.age
+    }
+
+    /// Zero-based index of the current generation
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Number of steps after which a generation ends (it might end sooner,
+    /// depending on the [`FoodPolicy`] and [`GenerationEnd`]).
+    ///
+    /// Panics if generations only end once enough food has been eaten, see
+    /// [`Self::try_generation_length()`].
+    pub fn generation_length(&self) -> usize {
+        self.try_generation_length()
+            .expect("generation doesn't have a fixed length")
+    }
+
+    /// Same as [`Self::generation_length()`], but returns `None` instead of
+    /// panicking
+    pub fn try_generation_length(&self) -> Option<usize> {
+        self.config.generation_end.steps()
+    }
+
+    /// Disabling evolution keeps the current animals around for good: when
+    /// a generation ends, they just get their satiation (& co.) reset and
+    /// the foods get scattered anew, which is handy for watching a trained
+    /// population
+    pub fn set_evolution_enabled(&mut self, enabled: bool) {
+        self.evolution_enabled = enabled;
+    }
+
+    pub fn evolution_enabled(&self) -> bool {
+        self.evolution_enabled
+    }
+
+    /// Returns genes of each animal's chromosome, in the same order as
+    /// [`World::animals()`]
+    pub fn export_chromosomes(&self) -> Vec<Vec<f32>> {
+        self.world
+            .animals
+            .iter()
+            .map(|animal| animal.as_chromosome().into_iter().collect())
+            .collect()
+    }
+
+    /// Returns how differently animals behave, as opposed to how different
+    /// their chromosomes are: each animal is let loose on its own for a few
+    /// steps, starting in the same spot of the same (random) world, and the
+    /// variances of where they end up and how much they eat get averaged -
+    /// zero means that all of them behave identically
+    pub fn behavioral_diversity(&self, rng: &mut dyn RngCore) -> f32 {
+        let seed = rng.next_u64();
+
+        // Keeping the mode keeps eyes (and so brains) the same shape as the
+        // animals' ones; it's just that there's nobody to hunt them
+        let config = Config {
+            n_animals: 1,
+            n_predators: 0,
+            ..self.config.clone()
+        };
+
+        let outcomes: Vec<[f32; 3]> = self
+            .world
+            .animals
+            .iter()
+            .map(|animal| {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                let animal = Animal::from_chromosome(&config, animal.as_chromosome(), &mut rng);
+                let world = World::from_animals(&config, vec![animal], &mut rng);
+                let mut sim = Simulation::from_world(config.clone(), world);
+
+                for _ in 0..BEHAVIOR_HORIZON {
+                    sim.advance(&mut rng);
+                }
+
+                let animal = &sim.world.animals[0];
+
+                [
+                    animal.position.x,
+                    animal.position.y,
+                    animal.satiation as f32,
+                ]
+            })
+            .collect();
+
+        if outcomes.is_empty() {
+            return 0.0;
+        }
+
+        let len = outcomes.len() as f32;
+
+        let variances = (0..3).map(|feature| {
+            let mean = outcomes.iter().map(|outcome| outcome[feature]).sum::<f32>() / len;
+
+            outcomes
+                .iter()
+                .map(|outcome| (outcome[feature] - mean).powi(2))
+                .sum::<f32>()
+                / len
+        });
+
+        variances.sum::<f32>() / 3.0
+    }
+
+    /// Fraction of the arena's cells that animals have visited during the
+    /// current generation; zero, unless [`Config::coverage_cells`] is set
+    pub fn coverage(&self) -> f32 {
+        if self.visits.is_empty() {
+            return 0.0;
+        }
+
+        let visited = self.visits.iter().filter(|&&visits| visits > 0).count();
+
+        visited as f32 / self.visits.len() as f32
+    }
+
+    /// Returns chromosome of the animal that ate the most in any of the
+    /// generations which have ended so far, along with its satiation
+    pub fn best_ever(&self) -> Option<(ga::Chromosome, usize)> {
+        self.best_ever.clone()
+    }
+
+    /// Starts the simulation over with random brains, positions and foods,
+    /// reusing the existing allocations where possible
+    pub fn reset(&mut self, rng: &mut dyn RngCore) {
+        let n_predators = match self.config.mode {
+            SimulationMode::SingleSpecies => 0,
+            SimulationMode::PredatorPrey => self.config.n_predators,
+        };
+
+        Self::reset_animals(
+            &mut self.world.animals,
+            self.config.n_animals,
+            &self.config,
+            rng,
+        );
+        Self::reset_animals(&mut self.world.predators, n_predators, &self.config, rng);
+
+        self.reseed_foods();
+        self.world.foods.truncate(self.config.n_foods_at(0));
+
+        let food_rng = Self::food_rng(&mut self.food_rng, rng);
+
+        for food in &mut self.world.foods {
+            *food = Food::random(&self.config, food_rng);
+        }
+
+        while self.world.foods.len() < self.config.n_foods_at(0) {
+            self.world.foods.push(Food::random(&self.config, food_rng));
+        }
+
+        for obstacle in &mut self.world.obstacles {
+            *obstacle = Obstacle::random(&self.config, rng);
+        }
+
+        self.world.age = 0;
+        self.generation = 0;
+        self.best_ever = None;
+        self.food_timers.clear();
+        self.non_finite_outputs = 0;
+        self.foods_eaten = 0;
+        self.visits.fill(0);
+    }
+
+    pub fn step(&mut self, rng: &mut dyn RngCore) -> Option<Statistics> {
+        self.step_with_report(rng).stats
+    }
+
+    /// Same as [`Self::step()`], but also reports what's happened during the
+    /// step, e.g. for visualization
+    pub fn step_with_report(&mut self, rng: &mut dyn RngCore) -> StepReport {
+        let (feeds, ended) = self.advance(rng);
+        let stats = if ended { Some(self.evolve(rng)) } else { None };
+
+        StepReport { feeds, stats }
+    }
+
+    /// Performs a single step, without evolving; returns what's been eaten
+    /// and whether the generation has ended
+    fn advance(&mut self, rng: &mut dyn RngCore) -> (Vec<FeedEvent>, bool) {
+        self.process_food_timers(rng);
+        let feeds = self.process_collisions(rng);
         self.process_brains();
         self.process_movement();
 
-        self.age += 1;
-        if self.age > GENERATION_LENGTH {
-            Some(self.evolve(rng))
-        } else {
-            None
-        }
+        self.world.age += 1;
+        self.foods_eaten += feeds.len();
+
+        let out_of_food = self.config.food_policy == FoodPolicy::Finite
+            && self.config.n_foods_at(self.generation) > 0
+            && self.world.foods.is_empty();
+
+        let ended = self.config.generation_end.has_ended(
+            self.world.age,
+            self.foods_eaten,
+            self.config.n_foods_at(self.generation),
+        );
+
+        (feeds, ended || out_of_food)
     }
 
-    pub fn train(&mut self, rng: &mut dyn RngCore) -> ga::Statistics {
+    pub fn train(&mut self, rng: &mut dyn RngCore) -> Statistics {
         loop {
             if let Some(summary) = self.step(rng) {
                 return summary;
@@ -64,95 +413,2535 @@ impl Simulation {
         }
     }
 
+    /// Runs `n` full generations, returning statistics of each of them
+    pub fn train_generations(&mut self, n: usize, rng: &mut dyn RngCore) -> Vec<Statistics> {
+        (0..n).map(|_| self.train(rng)).collect()
+    }
+
+    /// Runs given number of whole generations in one go, i.e. without any
+    /// steps to render in between, returning genetic statistics of each of
+    /// them - this is equivalent to calling [`Self::train()`] `generations`
+    /// times
+    pub fn fast_forward(
+        &mut self,
+        generations: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<ga::Statistics> {
+        self.train_generations(generations, rng)
+            .into_iter()
+            .map(|stats| stats.ga)
+            .collect()
+    }
+
+    /// Same as [`Self::train()`], but also passes index & statistics of the
+    /// generation that has just ended to `on_generation`, e.g. for logging
+    pub fn train_with(
+        &mut self,
+        rng: &mut dyn RngCore,
+        mut on_generation: impl FnMut(usize, &ga::Statistics),
+    ) -> Statistics {
+        let stats = self.train(rng);
+
+        on_generation(stats.generation(), stats.ga());
+        stats
+    }
+
+    /// Keeps running full generations until `stop` (given statistics of the
+    /// generation that has just ended, along with its zero-based index)
+    /// returns `true`, returning statistics of all of them
+    pub fn train_until(
+        &mut self,
+        mut stop: impl FnMut(&ga::Statistics, usize) -> bool,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Statistics> {
+        let mut stats = Vec::new();
+
+        loop {
+            let generation = self.train(rng);
+            let done = stop(generation.ga(), generation.generation());
+
+            stats.push(generation);
+
+            if done {
+                return stats;
+            }
+        }
+    }
+
+    /// Runs the rest of the current generation (just like [`Self::train()`]
+    /// would), returning positions of the animal that has eaten the most
+    /// after each of the generation's steps - except for the last one, which
+    /// replaces the animals with the next generation
+    pub fn record_best_trajectory(&mut self, rng: &mut dyn RngCore) -> Vec<(f32, f32)> {
+        let mut trajectories = vec![Vec::new(); self.world.animals.len()];
+
+        loop {
+            let (_, ended) = self.advance(rng);
+
+            if ended {
+                break;
+            }
+
+            for (trajectory, animal) in trajectories.iter_mut().zip(&self.world.animals) {
+                trajectory.push((animal.position.x, animal.position.y));
+            }
+        }
+
+        let best = self
+            .world
+            .animals
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, animal)| animal.satiation)
+            .map(|(idx, _)| idx);
+
+        self.evolve(rng);
+
+        best.map(|idx| trajectories.swap_remove(idx))
+            .unwrap_or_default()
+    }
+
+    pub fn step_seeded(&mut self) -> Option<Statistics> {
+        let mut rng = self.rng.clone();
+        let stats = self.step(&mut rng);
+
+        self.rng = rng;
+
+        if stats.is_some() {
+            self.reseed();
+        }
+
+        stats
+    }
+
+    pub fn train_seeded(&mut self) -> Statistics {
+        let mut rng = self.rng.clone();
+        let stats = self.train(&mut rng);
+
+        self.rng = rng;
+        self.reseed();
+        stats
+    }
+
+    /// Derives the owned rngs from the base seed and current generation
+    fn reseed(&mut self) {
+        let Some(base_seed) = self.base_seed else {
+            return;
+        };
+
+        self.rng = generation_rng(base_seed, self.generation);
+    }
+
+    /// Derives the food rngs from [`Config::food_seed`] and current
+    /// generation, so that foods of each generation only depend on them
+    fn reseed_foods(&mut self) {
+        let Some(food_seed) = self.config.food_seed else {
+            return;
+        };
+
+        let mut food_rng = generation_rng(food_seed, self.generation);
+
+        self.respawn_rng = Some(ChaCha8Rng::seed_from_u64(food_rng.gen()));
+        self.food_rng = Some(food_rng);
+    }
+
     fn process_movement(&mut self) {
-        for animal in &mut self.world.animals {
+        let animals = self
+            .world
+            .animals
+            .iter_mut()
+            .chain(self.world.predators.iter_mut());
+
+        for animal in animals {
             animal.position += animal.rotation * na::Vector2::new(0.0, animal.speed);
+            animal.distance_traveled += animal.speed;
+
+            if animal.speed <= self.config.idle_speed {
+                animal.idle_steps += 1;
+            }
+
+            self.config.boundary.apply(animal);
+
+            for obstacle in &self.world.obstacles {
+                animal.position = obstacle.push_out(animal.position);
+            }
 
-            animal.position.x = na::wrap(animal.position.x, 0.0, 1.0);
-            animal.position.y = na::wrap(animal.position.y, 0.0, 1.0);
+            animal.energy = (animal.energy - ENERGY_DECAY * animal.speed).max(0.0);
         }
-    }
 
-    fn process_brains(&mut self) {
-        for animal in &mut self.world.animals {
-            let vision =
-                animal
-                    .eye
-                    .process_vision(animal.position, animal.rotation, &self.world.foods);
+        if self.config.collision_enabled {
+            Self::separate(&mut self.world.animals, self.config.animal_radius);
+            Self::separate(&mut self.world.predators, self.config.animal_radius);
+        }
+
+        if let Some(n) = self.config.coverage_cells.filter(|&n| n > 0) {
+            for animal in &self.world.animals {
+                let cell = |coord: f32| ((coord.clamp(0.0, 1.0) * n as f32) as usize).min(n - 1);
+
+                self.visits[cell(animal.position.y) * n + cell(animal.position.x)] += 1;
+            }
+        }
+    }
 
-            let response = animal.brain.nn.propagate(vision);
+    fn reset_animals(animals: &mut Vec<Animal>, n: usize, config: &Config, rng: &mut dyn RngCore) {
+        animals.truncate(n);
 
-            let speed = response[0].clamp(-SPEED_ACCEL, SPEED_ACCEL);
-            let rotation = response[1].clamp(-ROTATION_ACCEL, ROTATION_ACCEL);
+        for animal in animals.iter_mut() {
+            animal.reset(config, rng);
+        }
 
-            animal.speed = (animal.speed + speed).clamp(SPEED_MIN, SPEED_MAX);
-            animal.rotation = na::Rotation2::new(animal.rotation.angle() + rotation);
+        while animals.len() < n {
+            animals.push(Animal::random(config, rng));
         }
     }
 
-    fn process_collisions(&mut self, rng: &mut dyn RngCore) {
-        for animal in &mut self.world.animals {
-            for food in &mut self.world.foods {
-                let distance = na::distance(&animal.position, &food.position);
+    /// Pushes overlapping animals apart, so that they end up (at least)
+    /// `2 * radius` away from each other
+    fn separate(animals: &mut [Animal], radius: f32) {
+        for i in 0..animals.len() {
+            let (head, tail) = animals.split_at_mut(i + 1);
+            let a = &mut head[i];
+
+            for b in tail {
+                let offset = b.position - a.position;
+                let distance = offset.norm();
+                let overlap = 2.0 * radius - distance;
 
-                if distance <= 0.01 {
-                    animal.satiation += 1;
-                    food.position = rng.gen();
+                if overlap <= 0.0 {
+                    continue;
                 }
+
+                // Animals lying exactly on top of each other have no natural
+                // direction to get pushed in, so pick an arbitrary one
+                let dir = if distance > 0.0 {
+                    offset / distance
+                } else {
+                    na::Vector2::x()
+                };
+
+                a.position -= dir * (overlap / 2.0);
+                b.position += dir * (overlap / 2.0);
             }
         }
     }
 
-    fn evolve(&mut self, rng: &mut dyn RngCore) -> ga::Statistics {
-        self.age = 0;
+    fn process_brains(&mut self) {
+        // Predators hunt prey, so that's what they see in place of food
+        let prey: Vec<Food> = if self.world.predators.is_empty() {
+            Vec::new()
+        } else {
+            self.world
+                .animals
+                .iter()
+                .map(|animal| Food {
+                    position: animal.position,
+                    kind: FoodKind::Good,
+                })
+                .collect()
+        };
 
-        let current_population: Vec<AnimalIndividual> = self
+        // ... and prey, on the other hand, keep an eye on predators
+        let predators: Vec<_> = self
             .world
-            .animals
+            .predators
             .iter()
-            .map(|animal| AnimalIndividual::from_animal(animal))
+            .map(|predator| predator.position)
             .collect();
 
-        let (evolved_population, stats) = self.ga.evolve(rng, &current_population);
+        self.non_finite_outputs += Self::process_brains_of(
+            &mut self.world.animals,
+            &self.world.foods,
+            &predators,
+            &self.world.obstacles,
+            self.config.motor_model,
+        );
+
+        self.non_finite_outputs += Self::process_brains_of(
+            &mut self.world.predators,
+            &prey,
+            &[],
+            &self.world.obstacles,
+            self.config.motor_model,
+        );
+    }
 
-        self.world.animals = evolved_population
-            .into_iter()
-            .map(|individual| AnimalIndividual::into_animal(individual, rng))
-            .collect();
+    fn process_brains_of(
+        animals: &mut [Animal],
+        foods: &[Food],
+        predators: &[na::Point2<f32>],
+        obstacles: &[Obstacle],
+        motor_model: MotorModel,
+    ) -> usize {
+        // Animals seeing each other look at where everyone was at the start
+        // of this step, not at where the ones processed earlier have moved
+        let positions: Vec<_> = if animals.iter().any(|animal| animal.eye.sense_animals()) {
+            animals.iter().map(|animal| animal.position).collect()
+        } else {
+            Vec::new()
+        };
 
-        for food in &mut self.world.foods {
-            food.position = rng.gen();
+        let process = |(idx, animal): (usize, &mut Animal)| {
+            let others = others(&positions, idx);
+
+            Self::process_brain(animal, foods, &others, predators, obstacles, motor_model)
+        };
+
+        // Each animal only reads the shared world and writes to itself, so the
+        // outcome doesn't depend on the order animals get processed in
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+
+            animals.par_iter_mut().enumerate().map(process).sum()
         }
 
-        stats
+        #[cfg(not(feature = "rayon"))]
+        {
+            animals.iter_mut().enumerate().map(process).sum()
+        }
     }
 
-    pub fn choose_best(&mut self, rng: &mut dyn RngCore) {
-        assert!(self.world.animals.len() > 1);
+    /// Lets animal see and decide where to go, returning the number of
+    /// non-finite outputs its brain has produced
+    fn process_brain(
+        animal: &mut Animal,
+        foods: &[Food],
+        others: &[na::Point2<f32>],
+        predators: &[na::Point2<f32>],
+        obstacles: &[Obstacle],
+        motor_model: MotorModel,
+    ) -> usize {
+        let vision = animal.eye.process_vision(
+            animal.position,
+            animal.rotation,
+            foods,
+            others,
+            predators,
+            obstacles,
+        );
 
-        let mut top_chromosome = Animal::random(rng).as_chromosome();
-        let mut top_satiation = 0;
+        let mut response = animal.brain.nn.propagate(vision.clone());
+        let non_finite_outputs = sanitize(&mut response);
 
-        for animal in &self.world.animals {
-            if animal.satiation > top_satiation {
-                top_chromosome = animal.as_chromosome();
-                top_satiation = animal.satiation;
+        animal.last_vision = vision;
+
+        motor_model.apply(animal, &MotorOutputs::new(response));
+
+        non_finite_outputs
+    }
+
+    fn process_food_timers(&mut self, rng: &mut dyn RngCore) {
+        let mut reappeared = 0;
+
+        self.food_timers.retain_mut(|timer| {
+            *timer = timer.saturating_sub(1);
+
+            if *timer == 0 {
+                reappeared += 1;
+                false
+            } else {
+                true
             }
+        });
+
+        let rng = Self::food_rng(&mut self.respawn_rng, rng);
+
+        for _ in 0..reappeared {
+            self.world.foods.push(Food::random(&self.config, rng));
         }
+    }
 
-        let new_animals: Vec<Animal> = (0..40)
-            .map(|_| {
-                let eye = Eye::default();
-                let brain = Brain::from_chromosome(top_chromosome.clone(), &eye);
+    fn process_collisions(&mut self, rng: &mut dyn RngCore) -> Vec<FeedEvent> {
+        let respawn = self.config.food_policy == FoodPolicy::Respawn;
+        let reward_fn = &self.config.reward_fn;
+        let eat_mode = self.config.eat_mode;
+        let age = self.world.age;
+        let mut eaten = vec![false; self.world.foods.len()];
+        let mut grid = SpatialGrid::new(self.world.foods.iter().map(|food| food.position));
+        let mut feeds = Vec::new();
 
-                Animal::new(eye, brain, rng)
-            })
-            .collect();
+        for (animal_index, animal) in self.world.animals.iter_mut().enumerate() {
+            // A starved animal can't eat anymore, which caps how much it
+            // contributes to the next generation
+            if animal.is_starved() {
+                continue;
+            }
 
-        self.world.animals = new_animals;
+            // Visiting foods in the same order as a plain loop over all of
+            // them would keep the outcome independent of the grid
+            let mut food_idxs = grid.nearby(animal.position, animal.radius);
 
-        for food in &mut self.world.foods {
+            if eat_mode == EatMode::NearestOne {
+                // Checking the exact distance here, since the grid only
+                // narrows foods down to the nearby cells
+                let foods = &self.world.foods;
+
+                food_idxs = food_idxs
+                    .into_iter()
+                    .filter(|&food_idx| !eaten[food_idx])
+                    .map(|food_idx| {
+                        (
+                            food_idx,
+                            na::distance(&animal.position, &foods[food_idx].position),
+                        )
+                    })
+                    .filter(|&(_, distance)| distance <= animal.radius)
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                    .map(|(food_idx, _)| food_idx)
+                    .into_iter()
+                    .collect();
+            }
+
+            for food_idx in food_idxs {
+                if eaten[food_idx] {
+                    continue;
+                }
+
+                let food = &mut self.world.foods[food_idx];
+                let position = food.position;
+
+                let food_rng = Self::food_rng(&mut self.respawn_rng, rng);
+
+                if Self::try_eat(animal, food, reward_fn, age, respawn, food_rng) {
+                    if respawn {
+                        grid.remove(food_idx, position);
+                        grid.insert(food_idx, food.position);
+                    } else {
+                        eaten[food_idx] = true;
+                    }
+
+                    feeds.push(FeedEvent {
+                        animal_index,
+                        food_index: food_idx,
+                    });
+                }
+            }
+        }
+
+        self.remove_eaten_foods(&eaten);
+        self.process_catches(rng);
+
+        feeds
+    }
+
+    /// Lets animal eat given food if it's within the animal's radius,
+    /// respawning the food if asked to
+    fn try_eat(
+        animal: &mut Animal,
+        food: &mut Food,
+        reward_fn: &RewardFn,
+        age: usize,
+        respawn: bool,
+        rng: &mut dyn RngCore,
+    ) -> bool {
+        if na::distance(&animal.position, &food.position) > animal.radius {
+            return false;
+        }
+
+        match food.kind {
+            FoodKind::Good => {
+                animal.energy = (animal.energy + ENERGY_PER_FOOD).min(ENERGY_MAX);
+                animal.feed(reward_fn);
+                animal.record_feed(age);
+            }
+            FoodKind::Poison => animal.unfeed(reward_fn),
+        }
+
+        if respawn {
             food.position = rng.gen();
         }
+
+        true
+    }
+
+    fn remove_eaten_foods(&mut self, eaten: &[bool]) {
+        if let FoodPolicy::Timed { delay } = self.config.food_policy {
+            let count = eaten.iter().filter(|&&eaten| eaten).count();
+
+            self.food_timers.extend(std::iter::repeat_n(delay, count));
+        }
+
+        let mut eaten = eaten.iter();
+
+        self.world.foods.retain(|_| !eaten.next().unwrap());
+    }
+
+    /// Lets each prey be caught by its nearest predator, if it's close enough
+    fn process_catches(&mut self, rng: &mut dyn RngCore) {
+        for prey in &mut self.world.animals {
+            let nearest = self
+                .world
+                .predators
+                .iter_mut()
+                .filter(|predator| !predator.is_starved())
+                .map(|predator| (na::distance(&prey.position, &predator.position), predator))
+                .min_by(|(a, _), (b, _)| a.total_cmp(b));
+
+            let Some((distance, predator)) = nearest else {
+                continue;
+            };
+
+            if distance <= CATCH_DISTANCE {
+                predator.energy = (predator.energy + ENERGY_PER_FOOD).min(ENERGY_MAX);
+                predator.feed(&self.config.reward_fn);
+                predator.record_feed(self.world.age);
+
+                prey.unfeed(&self.config.reward_fn);
+                prey.position = rng.gen();
+            }
+        }
+    }
+
+    /// Evolves both prey and (if any) predators, returning prey's statistics
+    fn evolve(&mut self, rng: &mut dyn RngCore) -> Statistics {
+        self.world.age = 0;
+        self.foods_eaten = 0;
+        self.visits.fill(0);
+
+        let generation = self.generation;
+        self.generation += 1;
+
+        self.update_best_ever();
+
+        let mut stats = if self.evolution_enabled {
+            self.evolve_populations(generation, rng)
+        } else {
+            self.restart_populations(generation)
+        };
+
+        stats.non_finite_outputs = std::mem::take(&mut self.non_finite_outputs);
+
+        self.reseed_foods();
+        self.restock_foods(rng);
+
+        stats
+    }
+
+    fn evolve_populations(&mut self, generation: usize, rng: &mut dyn RngCore) -> Statistics {
+        let (animals, stats) = Self::evolve_population(
+            generation,
+            &mut self.ga,
+            &self.config,
+            rng,
+            &self.world.animals,
+        );
+
+        self.world.animals = animals;
+
+        if !self.world.predators.is_empty() {
+            let (predators, _) = Self::evolve_population(
+                generation,
+                &mut self.predator_ga,
+                &self.config,
+                rng,
+                &self.world.predators,
+            );
+
+            self.world.predators = predators;
+        }
+
+        stats
+    }
+
+    /// Lets the same animals go again, when evolution is disabled
+    fn restart_populations(&mut self, generation: usize) -> Statistics {
+        let population: Vec<_> = self
+            .world
+            .animals
+            .iter()
+            .map(|animal| AnimalIndividual::from_animal(animal, &self.config))
+            .collect();
+
+        let stats = Statistics::new(
+            generation,
+            ga::Statistics::new(&population),
+            &self.world.animals,
+        );
+
+        for animal in self
+            .world
+            .animals
+            .iter_mut()
+            .chain(self.world.predators.iter_mut())
+        {
+            animal.restart();
+        }
+
+        stats
+    }
+
+    /// Scatters foods around for a new generation, bringing back the eaten
+    /// ones (if the food policy has removed them) and adjusting their number
+    /// to the food schedule
+    fn restock_foods(&mut self, rng: &mut dyn RngCore) {
+        let rng = Self::food_rng(&mut self.food_rng, rng);
+        let n_foods = self.config.n_foods_at(self.generation);
+
+        self.world.foods.truncate(n_foods);
+
+        for food in &mut self.world.foods {
+            food.position = rng.gen();
+        }
+
+        self.food_timers.clear();
+
+        while self.world.foods.len() < n_foods {
+            self.world.foods.push(Food::random(&self.config, rng));
+        }
+    }
+
+    fn update_best_ever(&mut self) {
+        let Some(best) = self
+            .world
+            .animals
+            .iter()
+            .max_by_key(|animal| animal.satiation)
+        else {
+            return;
+        };
+
+        let is_better = match &self.best_ever {
+            Some((_, satiation)) => best.satiation > *satiation,
+            None => true,
+        };
+
+        if is_better {
+            self.best_ever = Some((best.as_chromosome(), best.satiation));
+        }
+    }
+
+    fn evolve_population<S2>(
+        generation: usize,
+        ga: &mut ga::GeneticAlgorithm<S2>,
+        config: &Config,
+        rng: &mut dyn RngCore,
+        animals: &[Animal],
+    ) -> (Vec<Animal>, Statistics)
+    where
+        S2: ga::SelectionMethod,
+    {
+        let current_population: Vec<AnimalIndividual> = animals
+            .iter()
+            .map(|animal| AnimalIndividual::from_animal(animal, config))
+            .collect();
+
+        ga.set_generation(generation);
+
+        let (evolved_population, stats) = ga.evolve(rng, &current_population);
+        let stats = Statistics::new(generation, stats, animals);
+
+        let animals = evolved_population
+            .into_iter()
+            .map(|individual| individual.into_animal(config, rng))
+            .collect();
+
+        (animals, stats)
+    }
+
+    /// Seeds a new population from the single animal that has eaten the
+    /// most, picked at random if several of them are tied.
+    ///
+    /// If no animal has eaten anything, the one with the highest fitness (as
+    /// per [`Config::fitness_weights`]) gets picked instead.
+    pub fn choose_best(&mut self, rng: &mut dyn RngCore) {
+        assert!(self.world.animals.len() > 1);
+
+        let max_satiation = self
+            .world
+            .animals
+            .iter()
+            .map(|animal| animal.satiation)
+            .max()
+            .unwrap_or_default();
+
+        let scores: Vec<f32> = self
+            .world
+            .animals
+            .iter()
+            .map(|animal| {
+                if max_satiation > 0 {
+                    animal.satiation as f32
+                } else {
+                    let individual = AnimalIndividual::from_animal(animal, &self.config);
+
+                    ga::Individual::fitness(&individual)
+                }
+            })
+            .collect();
+
+        let top_score = scores.iter().copied().fold(f32::MIN, f32::max);
+
+        let ties: Vec<_> = (0..scores.len())
+            .filter(|&idx| scores[idx] == top_score)
+            .collect();
+
+        let top_chromosome = self.world.animals[ties[rng.gen_range(0..ties.len())]].as_chromosome();
+
+        self.repopulate(&[top_chromosome], rng);
+    }
+
+    /// Seeds a new population from chromosomes of the `k` animals that have
+    /// eaten the most, cycling through them - which preserves more variation
+    /// than [`Self::choose_best()`] does
+    pub fn choose_top_k(&mut self, k: usize, rng: &mut dyn RngCore) {
+        assert!(k >= 1);
+        assert!(k <= self.world.animals.len());
+
+        // Min-heap of the best animals seen so far, with the worst one on top;
+        // ties are resolved in favour of animals that come first
+        let mut top = BinaryHeap::with_capacity(k + 1);
+
+        for (idx, animal) in self.world.animals.iter().enumerate() {
+            top.push(Reverse((animal.satiation, Reverse(idx))));
+
+            if top.len() > k {
+                top.pop();
+            }
+        }
+
+        let chromosomes: Vec<_> = top
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((_, Reverse(idx)))| self.world.animals[idx].as_chromosome())
+            .collect();
+
+        self.repopulate(&chromosomes, rng);
+    }
+
+    /// Replaces the population with as many fresh animals carrying given
+    /// chromosomes (cycling through them) and scatters the foods
+    fn repopulate(&mut self, chromosomes: &[ga::Chromosome], rng: &mut dyn RngCore) {
+        let new_animals: Vec<Animal> = chromosomes
+            .iter()
+            .cycle()
+            .take(self.world.animals.len())
+            .map(|chromosome| {
+                let eye = Eye::from(&self.config);
+                let brain = Brain::from_chromosome(
+                    chromosome.clone(),
+                    &eye,
+                    &self.config.brain_hidden_layers,
+                    self.config.brain_activations,
+                );
+
+                Animal::new(eye, brain, rng).with_radius(self.config.food_collision_radius)
+            })
+            .collect();
+
+        self.world.animals = new_animals;
+
+        let rng = Self::food_rng(&mut self.food_rng, rng);
+
+        for food in &mut self.world.foods {
+            food.position = rng.gen();
+        }
+    }
+
+    /// Returns the rng foods should be placed with
+    fn food_rng<'a>(
+        food_rng: &'a mut Option<ChaCha8Rng>,
+        rng: &'a mut dyn RngCore,
+    ) -> &'a mut dyn RngCore {
+        match food_rng {
+            Some(food_rng) => food_rng,
+            None => rng,
+        }
+    }
+}
+
+/// Returns rng for given generation of a seeded simulation; each generation
+/// gets its own stream, with stream #0 being left for creating the world
+fn generation_rng(seed: u64, generation: usize) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    rng.set_stream(generation as u64 + 1);
+    rng
+}
+
+/// Returns all the positions but the one at `idx`, i.e. what the animal at
+/// `idx` sees of the others
+fn others(positions: &[na::Point2<f32>], idx: usize) -> Vec<na::Point2<f32>> {
+    positions
+        .iter()
+        .enumerate()
+        .filter(|&(other_idx, _)| other_idx != idx)
+        .map(|(_, &position)| position)
+        .collect()
+}
+
+/// Replaces non-finite brain outputs with zeros, returning how many of them
+/// there were - extreme weights can make brains go haywire and since a single
+/// NaN would poison animal's position for good, let's not trust them
+fn sanitize(response: &mut [f32]) -> usize {
+    let mut non_finite_outputs = 0;
+
+    for output in response {
+        if !output.is_finite() {
+            *output = 0.0;
+            non_finite_outputs += 1;
+        }
+    }
+
+    non_finite_outputs
+}
+
+fn default_ga() -> ga::GeneticAlgorithm<ga::RouletteWheelSelection> {
+    ga::GeneticAlgorithm::new(
+        ga::RouletteWheelSelection::new(),
+        ga::UniformCrossover,
+        ga::GaussianMutation::new(0.01, 0.3),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use std::sync::{Arc, Mutex};
+    use test_case::test_case;
+
+    fn simulation() -> Simulation {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        Simulation::random(Config::default(), &mut rng)
+    }
+
+    #[test]
+    fn export_chromosomes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 5,
+            brain_hidden_layers: vec![4, 3],
+            ..Default::default()
+        };
+
+        let sim = Simulation::random(config, &mut rng);
+        let chromosomes = sim.export_chromosomes();
+
+        // (2 * 9 inputs + bias) * 4 + (4 + bias) * 3 + (3 + bias) * 2 outputs
+        assert_eq!(chromosomes.len(), 5);
+        assert!(chromosomes.iter().all(|genes| genes.len() == 99));
+
+        for (genes, animal) in chromosomes.iter().zip(sim.world().animals()) {
+            assert_eq!(genes.as_slice(), animal.as_chromosome().as_slice());
+        }
+    }
+
+    #[test]
+    fn getters_reflect_state_after_step() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = simulation();
+
+        let positions_before: Vec<_> = sim.world().animals().iter().map(Animal::position).collect();
+
+        sim.world.animals[0].satiation = 3;
+        sim.step(&mut rng);
+
+        for (animal, position_before) in sim.world().animals().iter().zip(positions_before) {
+            assert_ne!(animal.position(), position_before);
+            assert_eq!(animal.position(), animal.position);
+            assert_eq!(animal.rotation(), animal.rotation);
+            assert!((SPEED_MIN..=SPEED_MAX).contains(&animal.speed()));
+        }
+
+        assert_eq!(sim.world().animals()[0].satiation(), 3);
+
+        for animal in sim.world().animals() {
+            assert_eq!(animal.last_vision().len(), animal.eye.inputs());
+        }
+
+        for (food, expected) in sim.world().foods().iter().zip(&sim.world.foods) {
+            assert_eq!(food.position(), expected.position);
+        }
+    }
+
+    #[test_case(MotorModel::Direct ; "direct")]
+    #[test_case(MotorModel::DifferentialDrive ; "differential drive")]
+    #[test_case(MotorModel::Inertial { mass: 3.0 } ; "inertial")]
+    fn debug_step_matches_process_brains(motor_model: MotorModel) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            motor_model,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+
+        for _ in 0..5 {
+            sim.step(&mut rng);
+        }
+
+        let before: Vec<_> = sim
+            .world
+            .animals
+            .iter()
+            .map(|animal| (animal.speed, animal.rotation.angle()))
+            .collect();
+
+        let traces: Vec<_> = sim
+            .world
+            .animals
+            .iter()
+            .map(|animal| animal.debug_step(&sim.world, &sim.config))
+            .collect();
+
+        sim.process_brains();
+
+        for ((animal, trace), (speed, angle)) in sim.world.animals.iter().zip(traces).zip(before) {
+            assert_eq!(trace.vision, animal.last_vision);
+            assert_eq!(trace.outputs.len(), 2);
+            assert_relative_eq!(speed + trace.speed_delta, animal.speed);
+
+            assert_eq!(
+                na::Rotation2::new(angle + trace.rotation_delta).angle(),
+                animal.rotation.angle()
+            );
+        }
+    }
+
+    #[test]
+    fn non_finite_brain_outputs_get_sanitized() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        // ReLU happens to swallow NaNs, so make sure they reach the outputs
+        let config = Config {
+            n_animals: 2,
+            brain_activations: BrainActivations {
+                hidden: nn::Activation::Tanh,
+                output: nn::Activation::Tanh,
+            },
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+        let animal = &mut sim.world.animals[0];
+
+        let mut genes: Vec<_> = animal.as_chromosome().iter().copied().collect();
+        genes[0] = f32::NAN;
+
+        animal.brain = Brain::from_chromosome(
+            genes.into_iter().collect(),
+            &animal.eye,
+            &sim.config.brain_hidden_layers,
+            sim.config.brain_activations,
+        );
+
+        for _ in 0..10 {
+            sim.step(&mut rng);
+        }
+
+        let animal = &sim.world.animals[0];
+
+        assert!(animal.speed.is_finite());
+        assert!(animal.rotation.angle().is_finite());
+        assert!(animal.position.x.is_finite() && animal.position.y.is_finite());
+
+        let stats = sim.train(&mut rng);
+
+        assert!(stats.non_finite_outputs() >= 10);
+        assert_eq!(sim.non_finite_outputs, 0);
+    }
+
+    #[test]
+    fn age_and_generation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 4,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+
+        assert_eq!((sim.age(), sim.generation()), (0, 0));
+
+        for generation in 0..2 {
+            for age in 1..=sim.generation_length() {
+                assert!(sim.step(&mut rng).is_none());
+                assert_eq!((sim.age(), sim.generation()), (age, generation));
+            }
+
+            assert!(sim.step(&mut rng).is_some());
+            assert_eq!((sim.age(), sim.generation()), (0, generation + 1));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn world_snapshot_round_trip() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = simulation();
+
+        for _ in 0..10 {
+            sim.step(&mut rng);
+        }
+
+        let snapshot = serde_json::to_string(sim.world()).unwrap();
+        let world: World = serde_json::from_str(&snapshot).unwrap();
+        let mut restored = Simulation::from_world(Config::default(), world);
+
+        assert_eq!(serde_json::to_string(restored.world()).unwrap(), snapshot);
+        assert_eq!(restored.age(), 10);
+
+        let mut rng_a = ChaCha8Rng::from_seed([1; 32]);
+        let mut rng_b = ChaCha8Rng::from_seed([1; 32]);
+
+        for _ in 0..100 {
+            sim.step(&mut rng_a);
+            restored.step(&mut rng_b);
+        }
+
+        assert_eq!(
+            serde_json::to_string(sim.world()).unwrap(),
+            serde_json::to_string(restored.world()).unwrap()
+        );
+    }
+
+    #[test]
+    fn same_seed_yields_same_world() {
+        let mut sim_a = Simulation::from_seed(42);
+        let mut sim_b = Simulation::from_seed(42);
+
+        for _ in 0..100 {
+            sim_a.step_seeded();
+            sim_b.step_seeded();
+        }
+
+        assert_eq!(
+            format!("{:?}", sim_a.world()),
+            format!("{:?}", sim_b.world())
+        );
+
+        let sim_c = Simulation::from_seed(43);
+
+        assert_ne!(
+            format!("{:?}", sim_a.world()),
+            format!("{:?}", sim_c.world())
+        );
+    }
+
+    #[test]
+    fn resume_seeded() {
+        let config = Config {
+            n_animals: 4,
+            ..Default::default()
+        };
+
+        // Same as `Simulation::from_seed()`, just with a smaller config
+        let seeded = |config: Config| {
+            let world = World::random(&config, &mut ChaCha8Rng::seed_from_u64(42));
+
+            Simulation::resume_seeded(config, world, 42, 0)
+        };
+
+        let mut sim_a = seeded(config.clone());
+        let mut sim_b = seeded(config.clone());
+
+        for _ in 0..5 {
+            sim_a.train_seeded();
+            sim_b.train_seeded();
+        }
+
+        // Pretend the world has been saved and the process restarted
+        let mut sim_b = Simulation::resume_seeded(config, sim_b.world, 42, 5);
+
+        for _ in 5..10 {
+            sim_a.train_seeded();
+            sim_b.train_seeded();
+        }
+
+        assert_eq!(sim_b.generation(), 10);
+        assert_eq!(
+            format!("{:?}", sim_a.world()),
+            format!("{:?}", sim_b.world())
+        );
+    }
+
+    #[test]
+    fn animals_see_each_other() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 2,
+            n_foods: 0,
+            eye_sense_animals: true,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+
+        sim.world.animals[0].position = na::Point2::new(0.5, 0.5);
+        sim.world.animals[0].rotation = na::Rotation2::new(0.0);
+        sim.world.animals[1].position = na::Point2::new(0.5, 0.6);
+        sim.world.animals[1].rotation = na::Rotation2::new(PI);
+
+        sim.process_brains();
+
+        let cells = sim.config.eye_cells;
+        let (foods, animals) = sim.world.animals[0].last_vision().split_at(2 * cells);
+
+        assert!(foods.iter().all(|&cell| cell == 0.0));
+        assert!(animals.iter().any(|&cell| cell > 0.0));
+    }
+
+    #[test]
+    fn animal_is_stopped_at_obstacle_boundary() {
+        let mut sim = simulation();
+
+        sim.world.animals.truncate(1);
+        sim.world.obstacles = vec![Obstacle::new(na::Point2::new(0.5, 0.5), 0.05)];
+
+        let animal = &mut sim.world.animals[0];
+        animal.position = na::Point2::new(0.5, 0.448);
+        animal.rotation = na::Rotation2::new(0.0);
+        animal.speed = SPEED_MAX;
+
+        for _ in 0..10 {
+            sim.process_movement();
+
+            let position = sim.world.animals[0].position;
+
+            assert_relative_eq!(position.x, 0.5);
+            assert_relative_eq!(position.y, 0.45);
+        }
+    }
+
+    #[test]
+    fn caught_prey_feeds_nearest_predator() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            mode: SimulationMode::PredatorPrey,
+            n_animals: 1,
+            n_predators: 2,
+            n_foods: 0,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+
+        sim.world.animals[0].position = na::Point2::new(0.5, 0.5);
+        sim.world.predators[0].position = na::Point2::new(0.5, 0.508);
+        sim.world.predators[1].position = na::Point2::new(0.5, 0.504);
+
+        sim.process_collisions(&mut rng);
+
+        assert_eq!(sim.world.predators[0].satiation(), 0);
+        assert_eq!(sim.world.predators[1].satiation(), 1);
+        assert_ne!(sim.world.animals[0].position(), na::Point2::new(0.5, 0.5));
+    }
+
+    #[test]
+    fn predators_evolve_with_prey() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            mode: SimulationMode::PredatorPrey,
+            n_predators: 3,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+        sim.train(&mut rng);
+
+        assert_eq!(sim.world().predators().len(), 3);
+        assert!(sim.world().predators().iter().all(|p| p.satiation() == 0));
+    }
+
+    #[test]
+    fn prey_sees_predator_in_front_of_it() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            mode: SimulationMode::PredatorPrey,
+            n_animals: 1,
+            n_predators: 1,
+            n_foods: 0,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+
+        sim.world.animals[0].position = na::Point2::new(0.5, 0.5);
+        sim.world.animals[0].rotation = na::Rotation2::new(0.0);
+        sim.world.predators[0].position = na::Point2::new(0.5, 0.6);
+
+        sim.process_brains();
+
+        let eye = &sim.world.animals[0].eye;
+        let (_, predator_vision) = sim.world.animals[0]
+            .last_vision()
+            .split_at(eye.inputs() - eye.cells());
+
+        assert!(predator_vision.iter().any(|&cell| cell > 0.0));
+    }
+
+    #[test]
+    fn with_predator_ga() {
+        let config = Config {
+            mode: SimulationMode::PredatorPrey,
+            n_predators: 3,
+            ..Default::default()
+        };
+
+        let predator_weights = |ga: Option<ga::GeneticAlgorithm<ga::RouletteWheelSelection>>| {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = Simulation::random(config.clone(), &mut rng);
+
+            if let Some(ga) = ga {
+                sim = sim.with_predator_ga(ga);
+            }
+
+            sim.train(&mut rng);
+
+            sim.world()
+                .predators()
+                .iter()
+                .map(|predator| format!("{:?}", predator.brain))
+                .collect::<Vec<_>>()
+        };
+
+        let ga = ga::GeneticAlgorithm::new(
+            ga::RouletteWheelSelection::new(),
+            ga::UniformCrossover,
+            ga::GaussianMutation::new(1.0, 1.0),
+        );
+
+        assert_ne!(predator_weights(None), predator_weights(Some(ga)));
+    }
+
+    mod food_policy {
+        use super::*;
+
+        fn simulation(food_policy: FoodPolicy) -> Simulation {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let config = Config {
+                n_animals: 3,
+                n_foods: 5,
+                food_policy,
+                ..Default::default()
+            };
+
+            Simulation::random(config, &mut rng)
+        }
+
+        #[test]
+        fn finite() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = simulation(FoodPolicy::Finite);
+
+            for expected in (0..5).rev() {
+                sim.world.foods[0].position = sim.world.animals[0].position;
+                sim.process_collisions(&mut rng);
+
+                assert_eq!(sim.world.foods.len(), expected);
+            }
+
+            assert_eq!(sim.world.animals[0].satiation, 5);
+        }
+
+        #[test]
+        fn finite_generation_ends_when_food_runs_out() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = simulation(FoodPolicy::Finite);
+
+            sim.world.foods.truncate(1);
+            sim.world.foods[0].position = sim.world.animals[0].position;
+
+            assert!(sim.step(&mut rng).is_some());
+            assert_eq!(sim.world.foods.len(), 5);
+        }
+
+        #[test]
+        fn timed() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = simulation(FoodPolicy::Timed { delay: 3 });
+
+            sim.world.foods[0].position = sim.world.animals[0].position;
+            sim.world.foods[1].position = sim.world.animals[1].position;
+            sim.process_collisions(&mut rng);
+
+            assert_eq!(sim.world.foods.len(), 3);
+
+            for expected in [3, 3, 5] {
+                sim.process_food_timers(&mut rng);
+
+                assert_eq!(sim.world.foods.len(), expected);
+            }
+        }
+    }
+
+    mod eat_mode {
+        use super::*;
+
+        fn simulation(eat_mode: EatMode) -> Simulation {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let config = Config {
+                n_animals: 1,
+                n_predators: 0,
+                n_foods: 2,
+                eat_mode,
+                ..Default::default()
+            };
+
+            let mut sim = Simulation::random(config, &mut rng);
+            let animal = &sim.world.animals[0];
+            let offset = na::Vector2::new(animal.radius / 2.0, 0.0);
+
+            sim.world.foods[0].position = animal.position + offset;
+            sim.world.foods[1].position = animal.position;
+            sim
+        }
+
+        #[test]
+        fn all() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = simulation(EatMode::All);
+
+            let feeds = sim.process_collisions(&mut rng);
+
+            assert_eq!(feeds.len(), 2);
+            assert_eq!(sim.world.animals[0].satiation, 2);
+        }
+
+        #[test]
+        fn nearest_one() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = simulation(EatMode::NearestOne);
+            let far_food = sim.world.foods[0].position;
+
+            let feeds = sim.process_collisions(&mut rng);
+
+            assert_eq!(
+                feeds,
+                [FeedEvent {
+                    animal_index: 0,
+                    food_index: 1
+                }]
+            );
+
+            assert_eq!(sim.world.animals[0].satiation, 1);
+            assert_eq!(sim.world.foods[0].position, far_food);
+
+            sim.process_collisions(&mut rng);
+
+            assert_eq!(sim.world.animals[0].satiation, 2);
+        }
+    }
+
+    mod generation_end {
+        use super::*;
+
+        fn simulation(generation_end: GenerationEnd) -> Simulation {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let config = Config {
+                n_animals: 3,
+                n_foods: 4,
+                generation_end,
+                ..Default::default()
+            };
+
+            Simulation::random(config, &mut rng)
+        }
+
+        #[test]
+        fn food_consumed_fraction() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = simulation(GenerationEnd::FoodConsumedFraction(0.5));
+
+            assert_eq!(sim.try_generation_length(), None);
+
+            sim.world.foods[0].position = sim.world.animals[0].position;
+
+            assert!(sim.step(&mut rng).is_none());
+
+            sim.world.foods[0].position = sim.world.animals[0].position;
+
+            assert!(sim.step(&mut rng).is_some());
+            assert_eq!((sim.age(), sim.generation()), (0, 1));
+        }
+
+        #[test]
+        fn food_consumed_fraction_ends_right_away_without_foods() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let config = Config {
+                n_animals: 3,
+                n_foods: 0,
+                generation_end: GenerationEnd::FoodConsumedFraction(0.5),
+                ..Default::default()
+            };
+
+            let mut sim = Simulation::random(config, &mut rng);
+
+            assert!(sim.step(&mut rng).is_some());
+            assert!(sim.step(&mut rng).is_some());
+            assert_eq!(sim.generation(), 2);
+        }
+
+        #[test]
+        #[should_panic(expected = "generation doesn't have a fixed length")]
+        fn food_consumed_fraction_has_no_generation_length() {
+            simulation(GenerationEnd::FoodConsumedFraction(0.5)).generation_length();
+        }
+
+        #[test]
+        fn either_ends_after_steps_when_nothing_gets_eaten() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let mut sim = simulation(GenerationEnd::Either {
+                steps: 3,
+                fraction: 0.5,
+            });
+
+            sim.world.foods.clear();
+
+            assert_eq!(sim.generation_length(), 3);
+
+            for _ in 0..3 {
+                assert!(sim.step(&mut rng).is_none());
+            }
+
+            assert!(sim.step(&mut rng).is_some());
+        }
+    }
+
+    mod food_collision_radius {
+        use super::*;
+
+        /// Puts a single food at given distance from the first animal and
+        /// returns whether the animal ate it
+        fn eats_food_at(sim: &mut Simulation, distance: f32) -> bool {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            sim.world.foods.truncate(1);
+            sim.world.foods[0].position =
+                sim.world.animals[0].position + na::Vector2::new(distance, 0.0);
+
+            sim.process_collisions(&mut rng)
+                .iter()
+                .any(|feed| feed.animal_index == 0)
+        }
+
+        #[test]
+        fn defaults_to_eat_distance() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = Simulation::random(Config::default(), &mut rng);
+
+            assert!(sim.world.animals.iter().all(|a| a.radius() == EAT_DISTANCE));
+            assert!(!eats_food_at(&mut sim, 0.03));
+        }
+
+        #[test]
+        fn configured() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let config = Config {
+                food_collision_radius: 0.05,
+                ..Default::default()
+            };
+
+            let mut sim = Simulation::random(config, &mut rng);
+
+            assert!(sim.world.animals.iter().all(|a| a.radius() == 0.05));
+            assert!(eats_food_at(&mut sim, 0.03));
+
+            sim.evolve(&mut rng);
+
+            assert!(sim.world.animals.iter().all(|a| a.radius() == 0.05));
+        }
+
+        #[test]
+        fn per_animal() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = Simulation::random(Config::default(), &mut rng);
+
+            let animal = Animal::random(&sim.config, &mut rng).with_radius(0.1);
+            let position = sim.world.animals[0].position;
+
+            sim.world.animals[0] = animal;
+            sim.world.animals[0].position = position;
+
+            assert_eq!(sim.world.animals[0].radius(), 0.1);
+            assert!(eats_food_at(&mut sim, 0.08));
+            assert!(!eats_food_at(&mut sim, 0.12));
+        }
+    }
+
+    #[test]
+    fn statistics_report_satiation_of_ended_generation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = simulation();
+
+        sim.world.animals.truncate(4);
+
+        for (animal, satiation) in sim.world.animals.iter_mut().zip([2, 0, 7, 3]) {
+            animal.satiation = satiation;
+        }
+
+        let stats = sim.evolve(&mut rng);
+
+        assert_eq!(stats.min_satiation(), 0);
+        assert_eq!(stats.max_satiation(), 7);
+        assert_relative_eq!(stats.mean_satiation(), 3.0);
+        assert!(sim.world.animals.iter().all(|animal| animal.satiation == 0));
+    }
+
+    #[test]
+    fn train_generations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 4,
+            n_foods: 4,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+
+        sim.train(&mut rng);
+
+        let stats = sim.train_generations(3, &mut rng);
+        let generations: Vec<_> = stats.iter().map(Statistics::generation).collect();
+
+        assert_eq!(generations, [1, 2, 3]);
+    }
+
+    #[test]
+    fn train_until() {
+        let config = Config {
+            n_animals: 10,
+            ..Default::default()
+        };
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(config.clone(), &mut rng);
+
+        let max_fitnesses: Vec<_> = sim
+            .train_generations(5, &mut rng)
+            .iter()
+            .map(|stats| stats.ga().max_fitness())
+            .collect();
+
+        let threshold = max_fitnesses[0];
+
+        let expected = max_fitnesses
+            .iter()
+            .position(|&fitness| fitness > threshold)
+            .unwrap();
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(config, &mut rng);
+
+        let stats = sim.train_until(|stats, _| stats.max_fitness() > threshold, &mut rng);
+
+        assert_eq!(stats.len(), expected + 1);
+        assert_eq!(sim.generation(), expected + 1);
+    }
+
+    #[test]
+    fn train_until_passes_generation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 4,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+        let mut seen = Vec::new();
+
+        let stats = sim.train_until(
+            |_, generation| {
+                seen.push(generation);
+                generation == 2
+            },
+            &mut rng,
+        );
+
+        assert_eq!(stats.len(), 3);
+        assert_eq!(seen, [0, 1, 2]);
+    }
+
+    #[test]
+    fn train_with() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 4,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+        let mut seen = Vec::new();
+
+        for _ in 0..2 {
+            let stats = sim.train_with(&mut rng, |generation, stats| {
+                seen.push((generation, stats.max_fitness()));
+            });
+
+            assert_eq!(
+                seen.last(),
+                Some(&(stats.generation(), stats.ga().max_fitness()))
+            );
+        }
+
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].0, 0);
+        assert_eq!(seen[1].0, 1);
+    }
+
+    #[test]
+    fn record_best_trajectory() {
+        let config = Config {
+            n_animals: 4,
+            ..Default::default()
+        };
+
+        let mut rng_a = ChaCha8Rng::from_seed(Default::default());
+        let mut sim_a = Simulation::random(config.clone(), &mut rng_a);
+
+        let mut rng_b = ChaCha8Rng::from_seed(Default::default());
+        let mut sim_b = Simulation::random(config, &mut rng_b);
+
+        let trajectory = sim_a.record_best_trajectory(&mut rng_a);
+
+        sim_b.train(&mut rng_b);
+
+        assert_eq!(trajectory.len(), GENERATION_LENGTH);
+        assert_eq!(sim_a.generation(), 1);
+        assert_eq!(
+            format!("{:?}", sim_a.world()),
+            format!("{:?}", sim_b.world())
+        );
+    }
+
+    #[test]
+    fn fast_forward_matches_repeated_train() {
+        let config = Config {
+            n_animals: 4,
+            n_foods: 4,
+            ..Default::default()
+        };
+
+        let fitnesses = |stats: &ga::Statistics| {
+            [
+                stats.min_fitness(),
+                stats.max_fitness(),
+                stats.avg_fitness(),
+                stats.median_fitness(),
+            ]
+        };
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(config.clone(), &mut rng);
+        let actual: Vec<_> = sim
+            .fast_forward(3, &mut rng)
+            .iter()
+            .map(fitnesses)
+            .collect();
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(config, &mut rng);
+        let expected: Vec<_> = (0..3)
+            .map(|_| fitnesses(sim.train(&mut rng).ga()))
+            .collect();
+
+        assert_eq!(actual, expected);
+        assert_eq!(sim.generation(), 3);
+    }
+
+    #[test]
+    fn reward_fn_shapes_reward() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 1,
+            n_foods: 1,
+            reward_fn: RewardFn::new(|satiation| 1.0 / (1 + satiation) as f32),
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+        let mut rewards = vec![0.0];
+
+        for _ in 0..3 {
+            sim.world.foods[0].position = sim.world.animals[0].position;
+            sim.process_collisions(&mut rng);
+
+            rewards.push(sim.world.animals[0].reward());
+        }
+
+        let gains: Vec<_> = rewards.windows(2).map(|pair| pair[1] - pair[0]).collect();
+
+        assert_eq!(sim.world.animals[0].satiation(), 3);
+        assert_relative_eq!(gains.as_slice(), [1.0, 0.5, 1.0 / 3.0].as_slice());
+
+        // Poison takes away the last food, along with its reward
+        sim.world.foods[0].kind = FoodKind::Poison;
+        sim.world.foods[0].position = sim.world.animals[0].position;
+        sim.process_collisions(&mut rng);
+
+        assert_eq!(sim.world.animals[0].satiation(), 2);
+        assert_relative_eq!(sim.world.animals[0].reward(), 1.5);
+    }
+
+    #[test]
+    fn poison_never_raises_fitness() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 1,
+            n_foods: 1,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+
+        let fitness = |sim: &Simulation| {
+            ga::Individual::fitness(&AnimalIndividual::from_animal(
+                &sim.world.animals[0],
+                &sim.config,
+            ))
+        };
+
+        sim.world.animals[0].energy = ENERGY_MAX / 2.0;
+        sim.world.foods[0].kind = FoodKind::Poison;
+
+        for _ in 0..3 {
+            let before = fitness(&sim);
+
+            sim.world.foods[0].position = sim.world.animals[0].position;
+            sim.process_collisions(&mut rng);
+
+            assert!(fitness(&sim) <= before);
+        }
+
+        assert_relative_eq!(sim.world.animals[0].energy, ENERGY_MAX / 2.0);
+    }
+
+    #[test_case(GENERATION_LENGTH ; "default generation")]
+    #[test_case(10 ; "short generation")]
+    fn standing_still_gets_penalized(steps: usize) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 2,
+            generation_end: GenerationEnd::FixedSteps(steps),
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config.clone(), &mut rng);
+
+        for _ in 0..steps {
+            sim.world.animals[0].speed = SPEED_MIN;
+            sim.world.animals[1].speed = SPEED_MAX;
+            sim.process_movement();
+        }
+
+        let penalty = |animal: &Animal| {
+            let fitness = |idle| {
+                let config = Config {
+                    fitness_weights: FitnessWeights {
+                        idle,
+                        ..Default::default()
+                    },
+                    ..config.clone()
+                };
+
+                ga::Individual::fitness(&AnimalIndividual::from_animal(animal, &config))
+            };
+
+            fitness(0.0) - fitness(1.0)
+        };
+
+        let [slow, fast] = &sim.world.animals[..] else {
+            unreachable!();
+        };
+
+        assert_eq!(slow.idle_steps(), steps);
+        assert_eq!(fast.idle_steps(), 0);
+        assert_relative_eq!(penalty(slow), 1.0);
+        assert_relative_eq!(penalty(fast), 0.0);
+    }
+
+    #[test]
+    fn first_feed_age_is_recorded_once() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 1,
+            n_foods: 1,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+
+        sim.world.age = 10;
+        sim.process_collisions(&mut rng);
+
+        assert_eq!(sim.world.animals[0].first_feed_age(), None);
+
+        for age in [20, 30] {
+            sim.world.age = age;
+            sim.world.foods[0].position = sim.world.animals[0].position;
+            sim.process_collisions(&mut rng);
+        }
+
+        assert_eq!(sim.world.animals[0].satiation(), 2);
+        assert_eq!(sim.world.animals[0].first_feed_age(), Some(20));
+    }
+
+    #[test]
+    fn grid_collisions_match_brute_force() {
+        fn process_collisions_brute_force(sim: &mut Simulation, rng: &mut dyn RngCore) {
+            for animal in &mut sim.world.animals {
+                if animal.is_starved() {
+                    continue;
+                }
+
+                for food in &mut sim.world.foods {
+                    <Simulation>::try_eat(
+                        animal,
+                        food,
+                        &sim.config.reward_fn,
+                        sim.world.age,
+                        true,
+                        rng,
+                    );
+                }
+            }
+
+            sim.process_catches(rng);
+        }
+
+        let config = Config {
+            n_foods: 400,
+            ..Default::default()
+        };
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim_grid = Simulation::random(config.clone(), &mut rng);
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim_brute = Simulation::random(config, &mut rng);
+
+        let mut rng_grid = ChaCha8Rng::from_seed([1; 32]);
+        let mut rng_brute = ChaCha8Rng::from_seed([1; 32]);
+
+        for _ in 0..100 {
+            sim_grid.process_collisions(&mut rng_grid);
+            sim_grid.process_brains();
+            sim_grid.process_movement();
+
+            process_collisions_brute_force(&mut sim_brute, &mut rng_brute);
+            sim_brute.process_brains();
+            sim_brute.process_movement();
+        }
+
+        let eaten: usize = sim_grid.world.animals.iter().map(Animal::satiation).sum();
+
+        assert!(eaten > 0);
+
+        assert_eq!(
+            format!("{:?}", sim_grid.world()),
+            format!("{:?}", sim_brute.world())
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_brains_match_serial_ones() {
+        fn process_brains_serially(sim: &mut Simulation) {
+            for animal in &mut sim.world.animals {
+                <Simulation>::process_brain(
+                    animal,
+                    &sim.world.foods,
+                    &[],
+                    &[],
+                    &sim.world.obstacles,
+                    sim.config.motor_model,
+                );
+            }
+        }
+
+        let config = Config {
+            n_animals: 200,
+            ..Default::default()
+        };
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim_parallel = Simulation::random(config.clone(), &mut rng);
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim_serial = Simulation::random(config, &mut rng);
+
+        let mut rng_parallel = ChaCha8Rng::from_seed([1; 32]);
+        let mut rng_serial = ChaCha8Rng::from_seed([1; 32]);
+
+        for _ in 0..200 {
+            sim_parallel.process_collisions(&mut rng_parallel);
+            sim_parallel.process_brains();
+            sim_parallel.process_movement();
+
+            sim_serial.process_collisions(&mut rng_serial);
+            process_brains_serially(&mut sim_serial);
+            sim_serial.process_movement();
+        }
+
+        assert_eq!(
+            format!("{:?}", sim_parallel.world()),
+            format!("{:?}", sim_serial.world())
+        );
+    }
+
+    #[test]
+    fn overlapping_animals_get_separated() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 2,
+            collision_enabled: true,
+            ..Default::default()
+        };
+
+        let radius = config.animal_radius;
+        let mut sim = Simulation::random(config, &mut rng);
+
+        sim.world.animals[1].position = sim.world.animals[0].position;
+        sim.world.animals[1].rotation = sim.world.animals[0].rotation;
+        sim.world.animals[1].speed = sim.world.animals[0].speed;
+        sim.world.animals[1].brain = Brain::from_chromosome(
+            sim.world.animals[0].as_chromosome(),
+            &sim.world.animals[0].eye,
+            &sim.config.brain_hidden_layers,
+            sim.config.brain_activations,
+        );
+
+        sim.step(&mut rng);
+
+        let distance = na::distance(
+            &sim.world.animals[0].position,
+            &sim.world.animals[1].position,
+        );
+
+        assert_relative_eq!(distance, 2.0 * radius, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn with_ga() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 4,
+            n_foods: 4,
+            ..Default::default()
+        };
+
+        let world = World::random(&config, &mut rng);
+
+        let ga = ga::GeneticAlgorithmBuilder::new()
+            .selection(ga::TournamentSelection::new(2))
+            .build();
+
+        let mut sim = Simulation::with_ga(world, ga, config);
+        let stats = sim.train(&mut rng);
+
+        assert_eq!(stats.generation(), 0);
+        assert_eq!(sim.world().animals().len(), 4);
+    }
+
+    mod coverage {
+        use super::*;
+
+        fn simulation() -> Simulation {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let config = Config {
+                n_animals: 1,
+                coverage_cells: Some(10),
+                ..Default::default()
+            };
+
+            Simulation::random(config, &mut rng)
+        }
+
+        #[test]
+        fn orbiting_animal() {
+            let mut sim = simulation();
+
+            for step in 0..1000 {
+                let angle = step as f32 / 10.0;
+
+                sim.world.animals[0].position =
+                    na::Point2::new(0.55 + 0.02 * angle.cos(), 0.55 + 0.02 * angle.sin());
+
+                sim.process_movement();
+            }
+
+            assert!(sim.coverage() <= 0.02);
+        }
+
+        #[test]
+        fn scattered_animal() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = simulation();
+
+            for _ in 0..1000 {
+                sim.world.animals[0].position = rng.gen();
+                sim.process_movement();
+            }
+
+            assert!(sim.coverage() >= 0.9);
+
+            sim.evolve(&mut rng);
+
+            assert_eq!(sim.coverage(), 0.0);
+        }
+
+        #[test]
+        fn disabled() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = Simulation::random(Config::default(), &mut rng);
+
+            sim.step(&mut rng);
+
+            assert_eq!(sim.coverage(), 0.0);
+        }
+    }
+
+    mod behavioral_diversity {
+        use super::*;
+
+        fn simulation(rng: &mut dyn RngCore) -> Simulation {
+            let config = Config {
+                n_animals: 5,
+                ..Default::default()
+            };
+
+            Simulation::random(config, rng)
+        }
+
+        #[test]
+        fn clones() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = simulation(&mut rng);
+            let chromosome = sim.world.animals[0].as_chromosome();
+
+            sim.repopulate(&[chromosome], &mut rng);
+
+            assert_relative_eq!(sim.behavioral_diversity(&mut rng), 0.0);
+        }
+
+        #[test]
+        fn random_animals() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let sim = simulation(&mut rng);
+
+            assert!(sim.behavioral_diversity(&mut rng) > 0.0);
+        }
+
+        #[test]
+        fn predator_prey() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let config = Config {
+                mode: SimulationMode::PredatorPrey,
+                n_animals: 4,
+                n_predators: 2,
+                ..Default::default()
+            };
+
+            let sim = Simulation::random(config, &mut rng);
+
+            assert!(sim.behavioral_diversity(&mut rng) > 0.0);
+        }
+    }
+
+    #[test]
+    fn disabled_evolution_keeps_chromosomes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 4,
+            generation_end: GenerationEnd::FixedSteps(10),
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+        let chromosomes = sim.export_chromosomes();
+
+        sim.set_evolution_enabled(false);
+
+        let stats = sim.train_generations(20, &mut rng);
+
+        assert_eq!(sim.generation(), 20);
+        assert_eq!(stats[19].generation(), 19);
+        assert_eq!(sim.export_chromosomes(), chromosomes);
+        assert!(sim
+            .world()
+            .animals()
+            .iter()
+            .all(|animal| animal.satiation() == 0));
+
+        sim.set_evolution_enabled(true);
+        sim.train(&mut rng);
+
+        assert_ne!(sim.export_chromosomes(), chromosomes);
+    }
+
+    #[test]
+    fn evolve_tells_ga_the_generation() {
+        struct RecordingMutation(Arc<Mutex<Vec<usize>>>);
+
+        impl ga::MutationMethod for RecordingMutation {
+            fn mutate(&self, _: &mut dyn RngCore, _: &mut ga::Chromosome) {
+                //
+            }
+
+            fn set_generation(&mut self, generation: usize) {
+                self.0.lock().unwrap().push(generation);
+            }
+        }
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let generations = Arc::new(Mutex::new(Vec::new()));
+
+        let config = Config {
+            n_animals: 4,
+            ..Default::default()
+        };
+
+        let world = World::random(&config, &mut rng);
+
+        let ga = ga::GeneticAlgorithmBuilder::new()
+            .mutation(RecordingMutation(generations.clone()))
+            .build();
+
+        let mut sim = Simulation::with_ga(world, ga, config);
+
+        for _ in 0..3 {
+            sim.evolve(&mut rng);
+        }
+
+        assert_eq!(*generations.lock().unwrap(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn reset() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = simulation();
+
+        sim.train(&mut rng);
+
+        for _ in 0..10 {
+            sim.step(&mut rng);
+        }
+
+        let before = format!("{:?}", sim.world());
+        let animals = sim.world.animals.as_ptr();
+
+        sim.world.animals.pop();
+        sim.reset(&mut rng);
+
+        assert_eq!(sim.age(), 0);
+        assert_eq!(sim.generation, 0);
+        assert!(sim.best_ever().is_none());
+        assert_eq!(sim.world.animals.len(), sim.config.n_animals);
+        assert_eq!(sim.world.animals.as_ptr(), animals);
+        assert!(sim.world.animals.iter().all(|animal| animal.satiation == 0));
+        assert_ne!(format!("{:?}", sim.world()), before);
+    }
+
+    #[test]
+    fn food_seed_decouples_foods_from_ga() {
+        fn foods(mutation_chance: f32) -> Vec<String> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let config = Config {
+                food_seed: Some(1234),
+                ..Default::default()
+            };
+
+            let world = World::random(&config, &mut rng);
+
+            let ga = ga::GeneticAlgorithmBuilder::new()
+                .mutation(ga::GaussianMutation::new(mutation_chance, 0.3))
+                .build();
+
+            let mut sim = Simulation::with_ga(world, ga, config);
+
+            (0..3)
+                .map(|_| {
+                    sim.train(&mut rng);
+                    format!("{:?}", sim.world().foods())
+                })
+                .collect::<Vec<_>>()
+        }
+
+        assert_eq!(foods(0.01), foods(0.5));
+    }
+
+    #[test_case(FoodPolicy::Respawn)]
+    #[test_case(FoodPolicy::Finite)]
+    fn food_count_follows_schedule(food_policy: FoodPolicy) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 4,
+            food_schedule: FoodSchedule::Sinusoidal {
+                min: 4,
+                max: 12,
+                period: 4,
+            },
+            food_policy,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+
+        assert_eq!(sim.world().foods().len(), 8);
+
+        for expected in [12, 8, 4, 8, 12] {
+            sim.train(&mut rng);
+
+            assert_eq!(sim.world().foods().len(), expected);
+        }
+    }
+
+    mod choose_best {
+        use super::*;
+
+        fn chosen(sim: &mut Simulation, rng: &mut dyn RngCore) -> ga::Chromosome {
+            sim.choose_best(rng);
+
+            let chosen = sim.world.animals[0].as_chromosome();
+
+            assert!(sim
+                .world
+                .animals
+                .iter()
+                .all(|animal| animal.as_chromosome().as_slice() == chosen.as_slice()));
+
+            chosen
+        }
+
+        #[test]
+        fn picks_among_tied_animals_at_random() {
+            let mut picks = [0; 2];
+
+            for seed in 0..20 {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                let mut sim = simulation();
+
+                sim.world.animals[1].satiation = 2;
+                sim.world.animals[4].satiation = 5;
+                sim.world.animals[6].satiation = 5;
+
+                let candidates = [
+                    sim.world.animals[4].as_chromosome(),
+                    sim.world.animals[6].as_chromosome(),
+                ];
+
+                let chosen = chosen(&mut sim, &mut rng);
+
+                let idx = candidates
+                    .iter()
+                    .position(|candidate| candidate.as_slice() == chosen.as_slice())
+                    .expect("chosen animal wasn't one of the best ones");
+
+                picks[idx] += 1;
+            }
+
+            assert!(picks.iter().all(|&n| n > 0));
+        }
+
+        #[test]
+        fn given_no_satiation_picks_the_fittest() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = simulation();
+
+            for animal in &mut sim.world.animals {
+                animal.energy = 0.5;
+            }
+
+            sim.world.animals[3].energy = 0.75;
+
+            let expected = sim.world.animals[3].as_chromosome();
+
+            assert_eq!(chosen(&mut sim, &mut rng).as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn keeps_population_size() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let config = Config {
+                n_animals: 3,
+                ..Default::default()
+            };
+
+            let world = World::random(&config, &mut rng);
+
+            let mut sim = Simulation::from_world(
+                Config {
+                    n_animals: 5,
+                    ..config
+                },
+                world,
+            );
+
+            chosen(&mut sim, &mut rng);
+
+            assert_eq!(sim.world.animals.len(), 3);
+        }
+    }
+
+    mod choose_top_k {
+        use super::*;
+
+        fn chromosomes(animals: &[Animal]) -> Vec<Vec<f32>> {
+            animals
+                .iter()
+                .map(|animal| animal.as_chromosome().iter().copied().collect())
+                .collect()
+        }
+
+        #[test]
+        fn keeps_every_chromosome_given_whole_population() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = simulation();
+            let n = sim.world.animals.len();
+
+            for (idx, animal) in sim.world.animals.iter_mut().enumerate() {
+                animal.satiation = idx % 3;
+            }
+
+            let mut expected = chromosomes(&sim.world.animals);
+
+            sim.choose_top_k(n, &mut rng);
+
+            let mut actual = chromosomes(&sim.world.animals);
+
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            actual.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn cycles_through_the_best() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = simulation();
+
+            sim.world.animals[3].satiation = 5;
+            sim.world.animals[7].satiation = 9;
+
+            let before = chromosomes(&sim.world.animals);
+
+            sim.choose_top_k(2, &mut rng);
+
+            let after = chromosomes(&sim.world.animals);
+
+            assert_eq!(after.len(), sim.config.n_animals);
+
+            for (idx, chromosome) in after.iter().enumerate() {
+                let expected = if idx % 2 == 0 { 7 } else { 3 };
+
+                assert_eq!(*chromosome, before[expected]);
+            }
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_zero() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            simulation().choose_top_k(0, &mut rng);
+        }
+    }
+
+    #[test]
+    fn best_ever_survives_worse_generations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = simulation();
+
+        assert!(sim.best_ever().is_none());
+
+        let mut expected = None;
+
+        for satiation in [3, 5, 2, 1] {
+            sim.world.animals[1].satiation = satiation;
+
+            if satiation == 5 {
+                expected = Some(sim.world.animals[1].as_chromosome());
+            }
+
+            sim.evolve(&mut rng);
+        }
+
+        let (chromosome, satiation) = sim.best_ever().unwrap();
+
+        assert_eq!(satiation, 5);
+        assert_eq!(chromosome.as_slice(), expected.unwrap().as_slice());
+    }
+
+    mod from_animals {
+        use super::*;
+
+        fn config() -> Config {
+            Config {
+                n_animals: 10,
+                ..Default::default()
+            }
+        }
+
+        /// Brain that makes animal run straight ahead at full speed, which
+        /// is quite an efficient strategy compared to a random one
+        fn runner(config: &Config, rng: &mut dyn RngCore) -> Animal {
+            let eye = Eye::from(config);
+            let inputs = eye.inputs();
+            let hidden = config.brain_hidden_layers[0];
+
+            let mut genes = vec![0.0; (inputs + 1) * hidden + (hidden + 1) * 2];
+            genes[(inputs + 1) * hidden] = 1.0;
+
+            let brain = Brain::from_chromosome(
+                genes.into_iter().collect(),
+                &eye,
+                &config.brain_hidden_layers,
+                config.brain_activations,
+            );
+
+            Animal::new(eye, brain, rng)
+        }
+
+        #[test]
+        fn good_brains_eat_more_than_random_ones() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let animals = (0..10).map(|_| runner(&config(), &mut rng)).collect();
+            let mut trained = Simulation::from_animals(animals, config(), &mut rng).unwrap();
+            let mut random = Simulation::random(config(), &mut rng);
+
+            let trained = trained.train(&mut rng);
+            let random = random.train(&mut rng);
+
+            assert!(
+                trained.mean_satiation() > random.mean_satiation(),
+                "{} <= {}",
+                trained.mean_satiation(),
+                random.mean_satiation()
+            );
+        }
+
+        #[test]
+        fn rejects_incompatible_animals() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let other_config = Config {
+                brain_hidden_layers: vec![3],
+                ..config()
+            };
+
+            let animals = vec![
+                Animal::random(&config(), &mut rng),
+                Animal::random(&other_config, &mut rng),
+            ];
+
+            assert_eq!(
+                Simulation::from_animals(animals, config(), &mut rng).err(),
+                Some(PopulationError::IncompatibleAnimal { index: 1 })
+            );
+
+            assert_eq!(
+                Simulation::from_animals(Vec::new(), config(), &mut rng).err(),
+                Some(PopulationError::Empty)
+            );
+        }
+    }
+
+    #[test]
+    fn clamped_animal_is_pinned_to_wall() {
+        let config = Config {
+            boundary: Boundary::Clamp,
+            ..Default::default()
+        };
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(config, &mut rng);
+
+        sim.world.animals.truncate(1);
+
+        let animal = &mut sim.world.animals[0];
+        animal.position = na::Point2::new(0.5, 0.998);
+        animal.rotation = na::Rotation2::new(0.0);
+        animal.speed = SPEED_MAX;
+
+        for _ in 0..3 {
+            sim.process_movement();
+
+            assert_relative_eq!(sim.world.animals[0].position.x, 0.5);
+            assert_relative_eq!(sim.world.animals[0].position.y, 1.0);
+        }
+    }
+
+    #[test]
+    fn feeding_is_reported() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = simulation();
+
+        let report = sim.step_with_report(&mut rng);
+
+        assert!(report.feeds.is_empty());
+        assert!(!report.generation_ended());
+
+        sim.world.foods[7].position = sim.world.animals[3].position;
+
+        let report = sim.step_with_report(&mut rng);
+
+        assert_eq!(
+            report.feeds,
+            [FeedEvent {
+                animal_index: 3,
+                food_index: 7
+            }]
+        );
+    }
+
+    #[test]
+    fn stationary_animal_slowly_loses_energy() {
+        let mut sim = simulation();
+
+        sim.world.animals.truncate(1);
+        sim.world.animals[0].speed = SPEED_MIN;
+
+        for _ in 0..100 {
+            sim.process_movement();
+        }
+
+        let energy = sim.world.animals[0].energy();
+
+        assert!(energy < ENERGY_MAX);
+        assert!(energy > 0.9 * ENERGY_MAX);
+    }
+
+    #[test]
+    fn starved_animal_does_not_eat() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = simulation();
+
+        sim.world.animals.truncate(1);
+        sim.world.animals[0].energy = 0.0;
+        sim.world.foods[0].position = sim.world.animals[0].position;
+
+        sim.process_collisions(&mut rng);
+
+        assert_eq!(sim.world.animals[0].satiation, 0);
     }
 }
@@ -0,0 +1,266 @@
+use crate::*;
+
+/// How brain's outputs get turned into animal's movement
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MotorModel {
+    /// Outputs are changes of speed and rotation, respectively
+    #[default]
+    Direct,
+
+    /// Outputs are speeds of the left and right wheel, respectively; going
+    /// straight requires both of them to be equal, while the difference
+    /// between them makes the animal turn
+    DifferentialDrive,
+
+    /// Outputs are forces changing speed and angular velocity, respectively;
+    /// the heavier the animal (`mass` must be at least `1.0`), the longer it
+    /// takes to speed up or turn - and the sharper it turns, the more speed
+    /// it loses
+    Inertial { mass: f32 },
+}
+
+/// Brain's outputs, named by what motor models make of them; there must be
+/// at least [`Self::LEN`] of them, any further ones are left for future use
+#[derive(Clone, Debug, PartialEq)]
+pub struct MotorOutputs {
+    outputs: Vec<f32>,
+}
+
+impl MotorOutputs {
+    /// Number of outputs motor models rely on
+    pub const LEN: usize = 2;
+
+    pub fn new(outputs: Vec<f32>) -> Self {
+        assert!(
+            outputs.len() >= Self::LEN,
+            "got {} motor output(s), but at least {} are required",
+            outputs.len(),
+            Self::LEN,
+        );
+
+        Self { outputs }
+    }
+
+    /// Change of speed (or force changing it, for [`MotorModel::Inertial`])
+    pub fn speed(&self) -> f32 {
+        self.outputs[0]
+    }
+
+    /// Change of rotation (or force changing it, for [`MotorModel::Inertial`])
+    pub fn rotation(&self) -> f32 {
+        self.outputs[1]
+    }
+
+    /// Speed of the left wheel, for [`MotorModel::DifferentialDrive`]
+    pub fn left_wheel(&self) -> f32 {
+        self.outputs[0]
+    }
+
+    /// Speed of the right wheel, for [`MotorModel::DifferentialDrive`]
+    pub fn right_wheel(&self) -> f32 {
+        self.outputs[1]
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        &self.outputs
+    }
+}
+
+/// What a motor model has decided to do with the animal
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Motion {
+    pub(crate) speed: f32,
+    pub(crate) rotation_delta: f32,
+    pub(crate) angular_velocity: f32,
+}
+
+impl MotorModel {
+    /// Panics if the model's parameters are out of range; called once, when
+    /// a simulation gets created, so that motions don't have to check them
+    pub(crate) fn validate(self) {
+        if let Self::Inertial { mass } = self {
+            assert!(
+                mass >= 1.0,
+                "inertial mass must be at least 1.0, got {mass}"
+            );
+        }
+    }
+
+    pub(crate) fn apply(self, animal: &mut Animal, outputs: &MotorOutputs) {
+        let motion = self.motion(animal, outputs);
+
+        animal.speed = motion.speed;
+        animal.angular_velocity = motion.angular_velocity;
+        animal.rotation = na::Rotation2::new(animal.rotation.angle() + motion.rotation_delta);
+    }
+
+    /// Returns how given animal should move, without moving it just yet
+    pub(crate) fn motion(self, animal: &Animal, outputs: &MotorOutputs) -> Motion {
+        match self {
+            Self::Direct => {
+                let speed = outputs.speed().clamp(-SPEED_ACCEL, SPEED_ACCEL);
+                let rotation = outputs.rotation().clamp(-ROTATION_ACCEL, ROTATION_ACCEL);
+
+                Motion {
+                    speed: (animal.speed + speed).clamp(SPEED_MIN, SPEED_MAX),
+                    rotation_delta: rotation,
+                    angular_velocity: animal.angular_velocity,
+                }
+            }
+
+            Self::DifferentialDrive => {
+                let left = outputs.left_wheel().clamp(-1.0, 1.0);
+                let right = outputs.right_wheel().clamp(-1.0, 1.0);
+
+                let linear = (left + right) / 2.0;
+                let angular = (right - left) / 2.0;
+
+                Motion {
+                    speed: (linear * SPEED_MAX).clamp(SPEED_MIN, SPEED_MAX),
+                    rotation_delta: angular * ROTATION_ACCEL,
+                    angular_velocity: animal.angular_velocity,
+                }
+            }
+
+            Self::Inertial { mass } => {
+                let speed = outputs.speed().clamp(-SPEED_ACCEL, SPEED_ACCEL);
+                let rotation = outputs.rotation().clamp(-ROTATION_ACCEL, ROTATION_ACCEL);
+
+                // Angular velocity follows the commanded one only gradually,
+                // so that a single sharp command turns the animal over a few
+                // steps instead of at once
+                let angular_velocity =
+                    animal.angular_velocity + (rotation - animal.angular_velocity) / mass;
+
+                Motion {
+                    speed: ((animal.speed + speed / mass) * angular_velocity.cos())
+                        .clamp(SPEED_MIN, SPEED_MAX),
+                    rotation_delta: angular_velocity,
+                    angular_velocity,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn animal() -> Animal {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut animal = Animal::random(&Config::default(), &mut rng);
+
+        animal.rotation = na::Rotation2::new(0.5);
+        animal.speed = SPEED_MIN;
+        animal
+    }
+
+    #[test]
+    fn direct() {
+        let mut animal = animal();
+
+        MotorModel::Direct.apply(&mut animal, &MotorOutputs::new(vec![0.002, 0.25]));
+
+        assert_relative_eq!(animal.speed, SPEED_MIN + 0.002);
+        assert_relative_eq!(animal.rotation.angle(), 0.75);
+    }
+
+    #[test]
+    fn differential_drive_with_equal_wheels_goes_straight() {
+        let mut animal = animal();
+
+        MotorModel::DifferentialDrive.apply(&mut animal, &MotorOutputs::new(vec![0.8, 0.8]));
+
+        assert_relative_eq!(animal.speed, 0.8 * SPEED_MAX);
+        assert_relative_eq!(animal.rotation.angle(), 0.5);
+    }
+
+    #[test]
+    fn differential_drive_with_faster_right_wheel_turns() {
+        let mut animal = animal();
+
+        MotorModel::DifferentialDrive.apply(&mut animal, &MotorOutputs::new(vec![0.2, 0.6]));
+
+        assert_relative_eq!(animal.speed, 0.4 * SPEED_MAX);
+        assert_relative_eq!(animal.rotation.angle(), 0.5 + 0.2 * ROTATION_ACCEL);
+    }
+
+    #[test]
+    fn inertial_turns_gradually() {
+        let mut animal = animal();
+        let model = MotorModel::Inertial { mass: 4.0 };
+
+        model.apply(&mut animal, &MotorOutputs::new(vec![0.0, ROTATION_ACCEL]));
+
+        let first_turn = animal.rotation.angle() - 0.5;
+
+        assert_relative_eq!(first_turn, ROTATION_ACCEL / 4.0);
+
+        let mut previous = animal.rotation.angle();
+
+        for _ in 0..3 {
+            model.apply(&mut animal, &MotorOutputs::new(vec![0.0, 0.0]));
+
+            let turn = animal.rotation.angle() - previous;
+
+            assert!(turn > 0.0 && turn < first_turn);
+            previous = animal.rotation.angle();
+        }
+    }
+
+    #[test]
+    fn inertial_with_unit_mass_behaves_like_direct_when_going_straight() {
+        let mut direct = animal();
+        let mut inertial = animal();
+
+        MotorModel::Direct.apply(&mut direct, &MotorOutputs::new(vec![0.002, 0.0]));
+        MotorModel::Inertial { mass: 1.0 }
+            .apply(&mut inertial, &MotorOutputs::new(vec![0.002, 0.0]));
+
+        assert_relative_eq!(inertial.speed, direct.speed);
+        assert_relative_eq!(inertial.rotation.angle(), direct.rotation.angle());
+    }
+
+    #[test]
+    fn inertial_sharp_turns_cost_speed() {
+        let mut animal = animal();
+
+        animal.speed = SPEED_MAX;
+
+        MotorModel::Inertial { mass: 1.0 }.apply(&mut animal, &MotorOutputs::new(vec![0.0, 1.0]));
+
+        assert_relative_eq!(animal.speed, SPEED_MAX * 1.0f32.cos());
+    }
+
+    #[test]
+    #[should_panic(expected = "inertial mass must be at least 1.0")]
+    fn simulation_rejects_inertial_mass_below_one() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            motor_model: MotorModel::Inertial { mass: 0.5 },
+            ..Default::default()
+        };
+
+        Simulation::random(config, &mut rng);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 2 are required")]
+    fn motor_outputs_require_two_outputs() {
+        MotorOutputs::new(vec![0.5]);
+    }
+
+    #[test]
+    fn motor_outputs_allow_extra_outputs() {
+        let outputs = MotorOutputs::new(vec![0.1, 0.2, 1.0]);
+
+        assert_eq!(outputs.speed(), 0.1);
+        assert_eq!(outputs.rotation(), 0.2);
+        assert_eq!(outputs.as_slice().len(), 3);
+    }
+}
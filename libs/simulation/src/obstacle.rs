@@ -0,0 +1,64 @@
+use crate::*;
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Obstacle {
+    pub(crate) position: na::Point2<f32>,
+    pub(crate) radius: f32,
+}
+
+impl Obstacle {
+    pub fn new(position: na::Point2<f32>, radius: f32) -> Self {
+        assert!(radius > 0.0);
+
+        Self { position, radius }
+    }
+
+    pub fn random(config: &Config, rng: &mut dyn RngCore) -> Self {
+        Self::new(rng.gen(), config.obstacle_radius)
+    }
+
+    pub fn position(&self) -> na::Point2<f32> {
+        self.position
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    pub(crate) fn contains(&self, point: na::Point2<f32>) -> bool {
+        na::distance(&self.position, &point) < self.radius
+    }
+
+    /// Returns whether this obstacle stands in the way of a straight line
+    /// going from `from` to `to`
+    pub(crate) fn blocks(&self, from: na::Point2<f32>, to: na::Point2<f32>) -> bool {
+        let segment = to - from;
+        let len_sq = segment.norm_squared();
+
+        let t = if len_sq > 0.0 {
+            ((self.position - from).dot(&segment) / len_sq).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        self.contains(from + segment * t)
+    }
+
+    /// Moves `point` onto this obstacle's boundary, if it lies inside of it
+    pub(crate) fn push_out(&self, point: na::Point2<f32>) -> na::Point2<f32> {
+        if !self.contains(point) {
+            return point;
+        }
+
+        let dir = point - self.position;
+
+        let dir = if dir.norm() > 0.0 {
+            dir.normalize()
+        } else {
+            na::Vector2::y()
+        };
+
+        self.position + dir * self.radius
+    }
+}
@@ -0,0 +1,96 @@
+use crate::*;
+
+/// Number of cells along each side of the grid
+const CELLS_PER_SIDE: usize = 64;
+
+/// Uniform grid over the unit square, bucketing items by their position so
+/// that proximity queries don't have to scan every one of them.
+///
+/// Points outside the unit square are clamped into the outermost cells.
+pub(crate) struct SpatialGrid {
+    cells: Vec<Vec<usize>>,
+}
+
+impl SpatialGrid {
+    pub(crate) fn new(points: impl IntoIterator<Item = na::Point2<f32>>) -> Self {
+        let mut this = Self {
+            cells: vec![Vec::new(); CELLS_PER_SIDE * CELLS_PER_SIDE],
+        };
+
+        for (idx, point) in points.into_iter().enumerate() {
+            this.insert(idx, point);
+        }
+
+        this
+    }
+
+    pub(crate) fn insert(&mut self, idx: usize, point: na::Point2<f32>) {
+        let cell = Self::cell_idx(Self::coords(point));
+
+        self.cells[cell].push(idx);
+    }
+
+    pub(crate) fn remove(&mut self, idx: usize, point: na::Point2<f32>) {
+        let cell = &mut self.cells[Self::cell_idx(Self::coords(point))];
+
+        if let Some(pos) = cell.iter().position(|&other| other == idx) {
+            cell.swap_remove(pos);
+        }
+    }
+
+    /// Returns (in ascending order) indices of items lying in the cells that
+    /// could contain points at most `radius` away from `point`
+    pub(crate) fn nearby(&self, point: na::Point2<f32>, radius: f32) -> Vec<usize> {
+        let (x, y) = Self::coords(point);
+        let reach = ((radius * CELLS_PER_SIDE as f32).ceil() as usize).max(1);
+        let mut items = Vec::new();
+
+        for cy in y.saturating_sub(reach)..=(y + reach).min(CELLS_PER_SIDE - 1) {
+            for cx in x.saturating_sub(reach)..=(x + reach).min(CELLS_PER_SIDE - 1) {
+                items.extend(&self.cells[Self::cell_idx((cx, cy))]);
+            }
+        }
+
+        items.sort_unstable();
+        items
+    }
+
+    fn coords(point: na::Point2<f32>) -> (usize, usize) {
+        let coord = |v: f32| {
+            ((v * CELLS_PER_SIDE as f32).floor().max(0.0) as usize).min(CELLS_PER_SIDE - 1)
+        };
+
+        (coord(point.x), coord(point.y))
+    }
+
+    fn cell_idx((x, y): (usize, usize)) -> usize {
+        y * CELLS_PER_SIDE + x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearby() {
+        let mut grid = SpatialGrid::new([
+            na::Point2::new(0.5, 0.5),
+            na::Point2::new(0.51, 0.49),
+            na::Point2::new(0.9, 0.1),
+            na::Point2::new(-0.2, 1.5),
+        ]);
+
+        assert_eq!(grid.nearby(na::Point2::new(0.5, 0.5), 0.01), [0, 1]);
+        assert_eq!(grid.nearby(na::Point2::new(0.9, 0.1), 0.01), [2]);
+        assert_eq!(grid.nearby(na::Point2::new(0.0, 1.0), 0.01), [3]);
+        assert!(grid.nearby(na::Point2::new(0.2, 0.2), 0.01).is_empty());
+        assert_eq!(grid.nearby(na::Point2::new(0.45, 0.5), 0.1), [0, 1]);
+
+        grid.remove(1, na::Point2::new(0.51, 0.49));
+        grid.insert(1, na::Point2::new(0.9, 0.11));
+
+        assert_eq!(grid.nearby(na::Point2::new(0.5, 0.5), 0.01), [0]);
+        assert_eq!(grid.nearby(na::Point2::new(0.9, 0.1), 0.01), [1, 2]);
+    }
+}
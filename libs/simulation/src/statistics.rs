@@ -0,0 +1,60 @@
+use crate::*;
+
+/// Summary of a generation that has just ended
+#[derive(Clone, Debug)]
+pub struct Statistics {
+    pub(crate) generation: usize,
+    pub(crate) ga: ga::Statistics,
+    pub(crate) min_satiation: usize,
+    pub(crate) max_satiation: usize,
+    pub(crate) mean_satiation: f32,
+    pub(crate) non_finite_outputs: usize,
+}
+
+impl Statistics {
+    pub(crate) fn new(generation: usize, ga: ga::Statistics, animals: &[Animal]) -> Self {
+        let satiations = || animals.iter().map(|animal| animal.satiation);
+
+        let mean_satiation = if animals.is_empty() {
+            0.0
+        } else {
+            satiations().sum::<usize>() as f32 / animals.len() as f32
+        };
+
+        Self {
+            generation,
+            ga,
+            min_satiation: satiations().min().unwrap_or_default(),
+            max_satiation: satiations().max().unwrap_or_default(),
+            mean_satiation,
+            non_finite_outputs: 0,
+        }
+    }
+
+    /// Zero-based index of the generation these statistics describe
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn ga(&self) -> &ga::Statistics {
+        &self.ga
+    }
+
+    pub fn min_satiation(&self) -> usize {
+        self.min_satiation
+    }
+
+    pub fn max_satiation(&self) -> usize {
+        self.max_satiation
+    }
+
+    pub fn mean_satiation(&self) -> f32 {
+        self.mean_satiation
+    }
+
+    /// How many times a brain (of any species) has yielded a NaN or infinite
+    /// output during this generation; such outputs get replaced with zeros
+    pub fn non_finite_outputs(&self) -> usize {
+        self.non_finite_outputs
+    }
+}
@@ -0,0 +1,73 @@
+use crate::*;
+use std::io;
+
+/// Writes per-generation statistics as CSV rows, e.g. for plotting them
+/// outside of Rust
+#[derive(Debug)]
+pub struct StatisticsLogger<W> {
+    writer: W,
+    header_written: bool,
+}
+
+impl<W: io::Write> StatisticsLogger<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            header_written: false,
+        }
+    }
+
+    /// Appends a row for given generation, preceded by the header if this is
+    /// the first row written
+    pub fn log(&mut self, generation: usize, stats: &ga::Statistics) -> io::Result<()> {
+        if !self.header_written {
+            writeln!(self.writer, "generation,min,max,avg,median")?;
+            self.header_written = true;
+        }
+
+        writeln!(
+            self.writer,
+            "{},{},{},{},{}",
+            generation,
+            stats.min_fitness(),
+            stats.max_fitness(),
+            stats.avg_fitness(),
+            stats.median_fitness(),
+        )
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 4,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::random(config, &mut rng);
+
+        for (animal, satiation) in sim.world.animals.iter_mut().zip([1, 3, 4, 8]) {
+            animal.satiation = satiation;
+            animal.reward = satiation as f32;
+        }
+
+        let stats = sim.evolve(&mut rng);
+        let mut logger = StatisticsLogger::new(Vec::new());
+
+        logger.log(0, stats.ga()).unwrap();
+
+        let csv = String::from_utf8(logger.into_inner()).unwrap();
+
+        assert_eq!(csv, "generation,min,max,avg,median\n0,2,9,5,4.5\n");
+    }
+}
@@ -0,0 +1,26 @@
+use crate::*;
+
+/// What has happened during a single step of the simulation
+#[derive(Clone, Debug, Default)]
+pub struct StepReport {
+    /// Foods eaten during this step, in the order they were eaten
+    pub feeds: Vec<FeedEvent>,
+
+    /// Present if this step has ended a generation
+    pub stats: Option<Statistics>,
+}
+
+impl StepReport {
+    pub fn generation_ended(&self) -> bool {
+        self.stats.is_some()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeedEvent {
+    pub animal_index: usize,
+
+    /// Index the food had at the beginning of the step (depending on the
+    /// [`FoodPolicy`], eaten foods might get removed afterwards)
+    pub food_index: usize,
+}
@@ -1,25 +1,174 @@
 use crate::*;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct World {
     pub(crate) animals: Vec<Animal>,
+    pub(crate) predators: Vec<Animal>,
     pub(crate) foods: Vec<Food>,
+    pub(crate) obstacles: Vec<Obstacle>,
+
+    /// Number of steps taken in the current generation; kept here (and not
+    /// in the simulation) so that a world saved mid-generation resumes
+    /// exactly where it's left off
+    pub(crate) age: usize,
 }
 
 impl World {
-    pub fn random(rng: &mut dyn RngCore) -> Self {
-        let animals = (0..40).map(|_| Animal::random(rng)).collect();
+    pub fn random(config: &Config, rng: &mut dyn RngCore) -> Self {
+        let animals = (0..config.n_animals)
+            .map(|_| Animal::random(config, rng))
+            .collect();
+
+        Self::from_animals(config, animals, rng)
+    }
+
+    /// Creates a world inhabited by given animals, with everything else
+    /// being random
+    pub(crate) fn from_animals(
+        config: &Config,
+        animals: Vec<Animal>,
+        rng: &mut dyn RngCore,
+    ) -> Self {
+        let predators = match config.mode {
+            SimulationMode::SingleSpecies => Vec::new(),
+            SimulationMode::PredatorPrey => (0..config.n_predators)
+                .map(|_| Animal::random(config, rng))
+                .collect(),
+        };
 
-        let foods = (0..40).map(|_| Food::random(rng)).collect();
+        let foods = (0..config.n_foods_at(0))
+            .map(|_| Food::random(config, rng))
+            .collect();
 
-        Self { animals, foods }
+        let obstacles = (0..config.n_obstacles)
+            .map(|_| Obstacle::random(config, rng))
+            .collect();
+
+        Self {
+            animals,
+            predators,
+            foods,
+            obstacles,
+            age: 0,
+        }
     }
 
     pub fn animals(&self) -> &[Animal] {
         &self.animals
     }
 
+    pub fn predators(&self) -> &[Animal] {
+        &self.predators
+    }
+
     pub fn foods(&self) -> &[Food] {
         &self.foods
     }
+
+    pub fn obstacles(&self) -> &[Obstacle] {
+        &self.obstacles
+    }
+
+    /// Returns animals packed as `[x, y, rotation, speed]` each, in the same
+    /// order as [`Self::animals()`] - i.e. stable until the next generation
+    pub fn animals_flat(&self) -> Vec<f32> {
+        self.animals
+            .iter()
+            .flat_map(|animal| {
+                [
+                    animal.position.x,
+                    animal.position.y,
+                    animal.rotation.angle(),
+                    animal.speed,
+                ]
+            })
+            .collect()
+    }
+
+    /// Returns foods packed as `[x, y]` each, in the same order as
+    /// [`Self::foods()`]
+    pub fn foods_flat(&self) -> Vec<f32> {
+        self.foods
+            .iter()
+            .flat_map(|food| [food.position.x, food.position.y])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn random() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 5,
+            n_foods: 3,
+            n_obstacles: 2,
+            ..Default::default()
+        };
+
+        let world = World::random(&config, &mut rng);
+
+        assert_eq!(world.animals().len(), 5);
+        assert!(world.predators().is_empty());
+        assert_eq!(world.foods().len(), 3);
+        assert_eq!(world.obstacles().len(), 2);
+    }
+
+    #[test]
+    fn random_with_predators() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            mode: SimulationMode::PredatorPrey,
+            n_animals: 5,
+            n_predators: 2,
+            ..Default::default()
+        };
+
+        let world = World::random(&config, &mut rng);
+
+        assert_eq!(world.animals().len(), 5);
+        assert_eq!(world.predators().len(), 2);
+    }
+
+    #[test]
+    fn flat() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            n_animals: 3,
+            n_foods: 2,
+            ..Default::default()
+        };
+
+        let world = World::random(&config, &mut rng);
+        let animals = world.animals_flat();
+        let foods = world.foods_flat();
+
+        assert_eq!(animals.len(), 3 * 4);
+        assert_eq!(foods.len(), 2 * 2);
+
+        for (packed, animal) in animals.chunks(4).zip(world.animals()) {
+            assert_eq!(
+                packed,
+                [
+                    animal.position().x,
+                    animal.position().y,
+                    animal.rotation().angle(),
+                    animal.speed(),
+                ]
+            );
+        }
+
+        for (packed, food) in foods.chunks(2).zip(world.foods()) {
+            assert_eq!(packed, [food.position().x, food.position().y]);
+        }
+    }
 }